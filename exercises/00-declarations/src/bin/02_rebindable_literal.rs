@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 // Allow unused_assignments: This exercise intentionally demonstrates reassignment,
@@ -29,7 +30,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("x = 10")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -36,7 +37,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("y = hello")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
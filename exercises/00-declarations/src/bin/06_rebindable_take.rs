@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 // Allow unused_assignments: This exercise intentionally demonstrates reassignment,
@@ -27,7 +28,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("y = world")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
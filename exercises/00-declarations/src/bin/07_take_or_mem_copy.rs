@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -35,7 +36,13 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise
+        .prints("x1 = 5, y1 = 5")
+        .prints("y2 = hello")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
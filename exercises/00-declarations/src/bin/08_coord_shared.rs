@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -31,7 +32,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("r1 = 5, r2 = 5, x = 5")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -33,7 +34,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints_in_order(&["r points to x: 5", "r points to z: 10"])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
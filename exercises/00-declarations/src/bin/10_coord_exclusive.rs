@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -39,7 +40,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("y = 10")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 // Allow unused_assignments: This exercise intentionally demonstrates reassignment,
@@ -41,7 +42,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("a = 100, b = 200")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 // Allow unused_assignments: This exercise intentionally demonstrates reassignment,
@@ -28,7 +29,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("x = 5, y = 100")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -18,7 +19,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("a = 1, b = 2")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
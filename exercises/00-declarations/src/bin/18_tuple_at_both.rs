@@ -1,3 +1,4 @@
+use check::Check;
 use spelled::explicit;
 
 fn example() {
@@ -23,7 +24,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints("a = 5, b = 10")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
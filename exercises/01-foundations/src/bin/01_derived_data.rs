@@ -2,6 +2,8 @@
 //!
 //! Physics creates distance. Distance forces copies. Copies require coherence.
 
+use check::Check;
+
 fn example() {
     // Rust prevents the coherence problem at compile time
     let mut x = 5;
@@ -16,21 +18,33 @@ fn example() {
     // The rule: you cannot have shared IDENTITY while mutation is possible
 }
 
-fn exercise() {
-    let mut x = 5;
-
-    // TODO: Create two paths to x (two references)
-    // TODO: Mutate through one path
-    // TODO: Observe through the other
+fn exercise() -> i32 {
+    // TODO: In compile_fail() below, create two paths to x (two references),
+    // mutate through one and observe through the other: uncomment it
     //
     // Does Rust allow this? Why or why not?
-    // Hint: Try creating two mutable references, or one mutable and one shared.
     // Map the error to: shared IDENTITY + mutation = coherence problem
+    //
+    // Then do it here the way Rust allows: observe through r1, mutate through
+    // r2 after r1's last use, and return x (6)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let mut x = 5;
+    let r1 = &x;
+    // let r2 = &mut x;  //~ E0502
+    // *r2 += 1;
+    println!("{}", r1);
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |x| *x == 6)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
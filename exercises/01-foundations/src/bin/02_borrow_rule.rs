@@ -2,6 +2,8 @@
 //!
 //! !(shared IDENTITY && mutation)
 
+use check::Check;
+
 fn example() {
     let mut x = 5;
 
@@ -18,24 +20,36 @@ fn example() {
     // The rule: you can have many &T OR one &mut T, never both simultaneously
 }
 
-fn exercise() {
+fn exercise() -> i32 {
+    // TODO: In compile_fail() below, uncomment the mutation of x while r1
+    // and r2 exist, and are used afterwards
+    //
+    // What does the error say? Map it to:
+    // The rule: !(shared IDENTITY && mutation)
+    //
+    // Then fix it here: move the mutation after the last use of r1 and r2,
+    // and return x (6)
+
+    todo!("Exercise incomplete");
+}
+
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
     let mut x = 5;
 
     let r1 = &x;     // Shared IDENTITY
     let r2 = &x;     // Another shared IDENTITY
 
-    // TODO: Try to mutate x here while r1 and r2 exist
-    // TODO: Then try to use r1 and r2
-    //
-    // What does the error say? Map it to:
-    // The rule: !(shared IDENTITY && mutation)
+    // x += 1;  //~ E0506
 
     println!("r1 = {}, r2 = {}", r1, r2);
+}
 
-    todo!("Exercise incomplete");
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |x| *x == 6)
 }
 
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! A lifetime is not memory duration. It's IDENTITY validity in TIME.
 
+use check::Check;
+
 fn example() {
     // Valid: reference lives within the scope of the data
     let x = 5;
@@ -20,22 +22,32 @@ fn example() {
     // Lifetimes ensure IDENTITY never outlives SPACE
 }
 
-fn exercise() {
-    // TODO: Write a function that tries to return a reference to a local variable
+fn exercise() -> i32 {
+    // TODO: Uncomment create_dangling() in compile_fail() below: a function
+    // that tries to return a reference to a local variable
     //
-    // fn create_dangling() -> &i32 {
-    //     let x = 5;        // SPACE created
-    //     &x                // IDENTITY to that SPACE
-    // }                     // SPACE ends. IDENTITY returned. Problem!
-    //
-    // Uncomment and observe the error.
     // What would happen if Rust allowed this?
     // The IDENTITY would outlive the SPACE it points to - a dangling reference.
+    //
+    // Fix it here: write a function that returns the value instead of a
+    // reference to it, and return what it gives (5)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    // fn create_dangling() -> &i32 {  //~ E0106
+    //     let x = 5;        // SPACE created
+    //     &x                // IDENTITY to that SPACE
+    // }                     // SPACE ends. IDENTITY returned. Problem!
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("5", |x| *x == 5)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! Shadowing exists because you can't undeclare a name mid-scope.
 
+use check::Check;
+
 struct Droppable(&'static str);
 
 impl Drop for Droppable {
@@ -29,7 +31,8 @@ fn example() {
 fn exercise() {
     println!("\n=== Your Turn ===");
 
-    // TODO: Create a Droppable, shadow it twice more, and predict the drop order
+    // TODO: Create Droppable("first"), shadow it with Droppable("second") and
+    // then Droppable("third"), and predict the drop order
     //
     // Before running, write down your prediction:
     // "I expect drops in this order: ___"
@@ -39,7 +42,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints_in_order(&["Dropping: third", "Dropping: second", "Dropping: first"])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! Move exists because you can't free a value mid-scope.
 
+use check::Check;
+
 fn example() {
     let x = vec![1, 2, 3];
     println!("x = {:?}", x);
@@ -20,20 +22,35 @@ fn example() {
     // Move simulates deletion by making the name unusable.
 }
 
-fn exercise() {
+fn exercise() -> String {
     // TODO: Create a String, move it to another binding, then shadow the original
     //
     // 1. let s = String::from("hello");
     // 2. Move s to a new binding
-    // 3. Try to use the original s (see the error)
-    // 4. Shadow s with something else
+    // 3. Shadow s with something else
+    // 4. Return the new binding
+    //
+    // Using the original s after the move is in compile_fail() below:
+    // uncomment it and see the error.
     //
     // What does this tell you about names vs IDENTITY?
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let s = String::from("hello");
+    let t = s;
+    // println!("{}", s);  //~ E0382
+    println!("{}", t);
+}
+
+fn check(exercise: Check<String>) -> Check<String> {
+    exercise.returns("\"hello\"", |moved| moved == "hello")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
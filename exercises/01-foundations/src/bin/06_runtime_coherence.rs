@@ -4,6 +4,8 @@
 
 use std::cell::RefCell;
 
+use check::Check;
+
 fn example() {
     let data = RefCell::new(5);
 
@@ -45,7 +47,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.panics("already borrowed")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -4,6 +4,8 @@
 
 use std::thread;
 
+use check::Check;
+
 fn example() {
     // This works: move ownership into the thread
     let x = vec![1, 2, 3];
@@ -31,27 +33,38 @@ fn example() {
     println!("Main can still read: {:?}", shared);
 }
 
-fn exercise() {
-    let mut x = 5;
-
-    // TODO: Uncomment and observe the error:
-    // let handle = thread::spawn(|| {
-    //     x += 1;  // Try to mutate from another TIME line
-    // });
-    // handle.join().unwrap();
+fn exercise() -> i32 {
+    // TODO: Uncomment the spawn in compile_fail() below and observe the error
     //
     // What error do you get?
     // Map it to: IDENTITY crossing TIME line boundaries requires Send/Sync
     // The closure captures x, but:
     // - x might not live long enough (lifetime)
     // - x might be accessed from two TIME lines (data race)
+    //
+    // Then fix it here: move x into the thread, increment it there, and
+    // return the thread's x through join (6)
+
+    todo!("Exercise incomplete");
+}
+
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let mut x = 5;
+
+    // let handle = thread::spawn(|| {  //~ E0373
+    //     x += 1;  // Try to mutate from another TIME line
+    // });
+    // handle.join().unwrap();
 
     println!("x = {}", x);
+}
 
-    todo!("Exercise incomplete");
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |x| *x == 6)
 }
 
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,8 @@
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+use check::Check;
+
 fn example() {
     let counter = Arc::new(Mutex::new(0));
     let mut handles = vec![];
@@ -31,10 +33,10 @@ fn example() {
     // Mutex handles TIME synchronization
 }
 
-fn exercise() {
+fn exercise() -> i32 {
     // TODO: Create your own Arc<Mutex<_>> counter
     // TODO: Spawn 10 threads that each increment it
-    // TODO: Print the final value
+    // TODO: Print the final value and return it
     //
     // Questions:
     // - What guarantees does Mutex provide?
@@ -44,7 +46,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("10", |count| *count == 10)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
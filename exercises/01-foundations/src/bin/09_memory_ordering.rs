@@ -6,6 +6,8 @@ use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::thread;
 use std::hint::black_box;
 
+use check::Check;
+
 static DATA: AtomicI32 = AtomicI32::new(0);
 static FLAG: AtomicBool = AtomicBool::new(false);
 
@@ -37,13 +39,15 @@ fn example() {
     // Without these orderings, DATA might not be visible yet!
 }
 
-fn exercise() {
+fn exercise() -> (usize, usize) {
     // Reset
     DATA.store(0, Ordering::SeqCst);
     FLAG.store(false, Ordering::SeqCst);
 
     // TODO: Modify this to use Relaxed ordering on both FLAG operations
-    // TODO: Run multiple times - do you always see DATA = 42?
+    // TODO: Run it 1000 times in a loop - do you always see DATA = 42?
+    // TODO: Count the runs that saw 42 and the runs that didn't, and return
+    // the two counts
     //
     // With Relaxed: DATA might not be 42 (reordering, visibility)
     // With Release/Acquire: DATA guaranteed to be 42
@@ -52,7 +56,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, usize)>) -> Check<(usize, usize)> {
+    exercise.returns("two counts adding up to 1000 runs", |(fortytwo, other)| fortytwo + other == 1000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! Different languages constrain different axes.
 
+use check::Check;
+
 fn example() {
     // Rust constrains IDENTITY (ownership, borrowing)
     rust_way();
@@ -29,13 +31,14 @@ fn immutable_way() {
     // Trade: more allocations, but simpler reasoning about IDENTITY
 }
 
-fn exercise() {
+fn exercise() -> i32 {
     // TODO: Implement a third approach - message passing style
     //
     // Instead of sharing mutable state:
     // - Create a channel (use std::sync::mpsc)
-    // - Have one "owner" thread that holds the state
-    // - Other threads send messages to request changes
+    // - Have one "owner" thread that holds the state, starting at 5
+    // - Other threads send messages to request changes, adding 5 in total
+    // - Once every sender is gone, the owner hands back the state: return it
     //
     // This is Erlang/Go's approach: constrain SPACE sharing, use TIME (messages)
     //
@@ -47,7 +50,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("10, like the other two ways", |x| *x == 10)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -3,6 +3,8 @@
 //! const: no SPACE exists. Value inlined at each use.
 //! static: one SPACE, one address, lives for program TIME.
 
+use check::Check;
+
 const CONST_VAL: i32 = 100;
 static STATIC_VAL: i32 = 100;
 
@@ -24,10 +26,11 @@ fn example() {
     // static: addresses always same (one fixed SPACE)
 }
 
-fn exercise() {
+fn exercise() -> (*const i32, *const i32) {
     // TODO: Create your own const and static values
     // TODO: Take their addresses multiple times
     // TODO: Verify the pattern: const may differ, static always same
+    // TODO: Return two of your static's addresses
     //
     // Questions:
     // - Why does const have no fixed SPACE?
@@ -36,7 +39,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(*const i32, *const i32)>) -> Check<(*const i32, *const i32)> {
+    exercise.returns("the same address twice", |(first, second)| first == second)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
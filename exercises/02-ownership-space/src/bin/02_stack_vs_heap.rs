@@ -3,6 +3,8 @@
 //! Stack: SPACE tied to scope TIME. LIFO.
 //! Heap: SPACE with independent TIME. Flexible.
 
+use check::Check;
+
 fn example() {
     let stack_val = 5;           // SPACE on stack
     let heap_val = Box::new(5);  // SPACE on heap, IDENTITY on stack
@@ -19,9 +21,11 @@ fn example() {
     // The heap value can be moved out; the stack value cannot.
 }
 
-fn exercise() {
+fn exercise() -> (usize, usize) {
     // TODO: Create several stack values and heap values
     // TODO: Print their addresses and observe the pattern
+    // TODO: Return how far apart two stack values are, and how far a stack
+    //       value is from a Box's contents (abs_diff of the addresses)
     //
     // Try:
     // - Multiple stack variables (should be close together)
@@ -33,7 +37,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, usize)>) -> Check<(usize, usize)> {
+    exercise.returns("two stack values closer together than a stack value and a Box's contents", |(near, far)| near < far)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
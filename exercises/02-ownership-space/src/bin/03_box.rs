@@ -3,6 +3,8 @@
 //! Box<T>: unique IDENTITY to heap SPACE
 //! Owner ends SPACE's TIME when dropped
 
+use check::Check;
+
 fn example() {
     let b1 = Box::new(5);
     println!("b1 = {}", b1);
@@ -16,11 +18,11 @@ fn example() {
     // There's only ever one owner of the heap SPACE
 }
 
-fn exercise() {
+fn exercise() -> Box<String> {
     // TODO: Create a Box<String>
-    // TODO: Move it to another binding
-    // TODO: Try to use the original (see the error)
-    // TODO: Verify only one owner exists at a time
+    // TODO: Move it to another binding, and return that binding
+    // TODO: Verify only one owner exists at a time: using the original after
+    //       the move is in compile_fail() below; uncomment it
     //
     // Bonus: What happens if you clone the Box instead of moving?
     // let b2 = b1.clone();
@@ -28,7 +30,19 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let b1 = Box::new(String::from("hello"));
+    let b2 = b1;
+    // println!("{}", b1);  //~ E0382
+    println!("{}", b2);
+}
+
+fn check(exercise: Check<Box<String>>) -> Check<Box<String>> {
+    exercise.returns("the moved Box, holding \"hello\"", |b2| **b2 == "hello")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
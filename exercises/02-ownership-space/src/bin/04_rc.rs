@@ -5,6 +5,8 @@
 
 use std::rc::Rc;
 
+use check::Check;
+
 fn example() {
     let a = Rc::new(5);
     println!("Created a, count: {}", Rc::strong_count(&a));
@@ -26,11 +28,12 @@ fn example() {
     // Map to: counting IDENTITYs to decide SPACE lifetime
 }
 
-fn exercise() {
+fn exercise() -> Vec<usize> {
     // TODO: Create an Rc<Vec<i32>>
     // TODO: Clone it multiple times
     // TODO: Print the strong_count at each step
     // TODO: Drop some clones and observe the count decrease
+    // TODO: Return the counts you printed, in order
     //
     // Questions:
     // - When is the Vec actually freed?
@@ -39,7 +42,14 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<usize>>) -> Check<Vec<usize>> {
+    exercise.returns("counts that start at 1, rise with each clone and fall with each drop", |counts| {
+        let most = counts.iter().max().copied().unwrap_or(0);
+        counts.first() == Some(&1) && most > 1 && counts.last() < Some(&most)
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,9 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashMap;
+
+use check::Check;
 
 fn example() {
     let shared = Rc::new(RefCell::new(vec![1, 2, 3]));
@@ -29,18 +32,25 @@ fn example() {
     // RefCell ensures they don't conflict in TIME.
 }
 
-fn exercise() {
+fn exercise() -> Vec<HashMap<String, i32>> {
     // TODO: Create an Rc<RefCell<HashMap<String, i32>>>
     // TODO: Clone it to multiple owners
     // TODO: Insert values through different owners
-    // TODO: Verify all owners see the same data
+    // TODO: Verify all owners see the same data: return a clone of the map
+    //       as each owner sees it
     //
     // Bonus: Try to hold two borrow_mut() at once - what happens?
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<HashMap<String, i32>>>) -> Check<Vec<HashMap<String, i32>>> {
+    exercise.returns("the same map, with every insert, from each of at least two owners", |maps| {
+        maps.len() >= 2 && maps[0].len() >= 2 && maps.iter().all(|map| *map == maps[0])
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
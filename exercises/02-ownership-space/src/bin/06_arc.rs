@@ -6,6 +6,8 @@
 use std::sync::Arc;
 use std::thread;
 
+use check::Check;
+
 fn example() {
     let data = Arc::new(vec![1, 2, 3]);
 
@@ -20,22 +22,33 @@ fn example() {
     // Arc allows IDENTITY to cross TIME line boundaries (Send)
 }
 
-fn exercise() {
-    // TODO: Try the same with Rc instead of Arc (uncomment below)
-    //
-    // use std::rc::Rc;
-    // let rc_data = Rc::new(vec![1, 2, 3]);
-    // let rc_clone = Rc::clone(&rc_data);
-    // thread::spawn(move || { println!("{:?}", rc_clone); });
+fn exercise() -> i32 {
+    // TODO: Try the same with Rc instead of Arc: uncomment the spawn in
+    // compile_fail() below
     //
     // What error do you get?
     // Map to: Rc is not Send - can't cross thread boundaries
     // Arc uses atomic operations to be thread-safe
+    //
+    // Then do it here with Arc: share vec![1, 2, 3] with a thread, sum it
+    // there, and return the sum through join (6)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    use std::rc::Rc;
+    let rc_data = Rc::new(vec![1, 2, 3]);
+    let rc_clone = Rc::clone(&rc_data);
+    // thread::spawn(move || { println!("{:?}", rc_clone); });  //~ E0277
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |sum| *sum == 6)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
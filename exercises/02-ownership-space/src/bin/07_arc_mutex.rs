@@ -7,6 +7,8 @@
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+use check::Check;
+
 fn example() {
     let data = Arc::new(Mutex::new(0));
 
@@ -31,10 +33,10 @@ fn example() {
     // The i32: the SPACE
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     // TODO: Create an Arc<Mutex<Vec<i32>>>
-    // TODO: Spawn 10 threads that each push their thread number
-    // TODO: Print the final Vec
+    // TODO: Spawn 10 threads that each push their thread number, 0 to 9
+    // TODO: Print the final Vec and return it
     //
     // Questions:
     // - What order do the numbers appear in?
@@ -44,7 +46,15 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("0 to 9, in some order", |numbers| {
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted == (0..10).collect::<Vec<i32>>()
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,12 @@
 
 use std::rc::{Rc, Weak};
 
+use check::Check;
+
+// Counts allocations, for the leak check in check()
+#[global_allocator]
+static ALLOCATOR: check::CountingAllocator = check::CountingAllocator;
+
 fn example() {
     let strong = Rc::new(5);
     let weak: Weak<i32> = Rc::downgrade(&strong);
@@ -29,12 +35,16 @@ fn example() {
     // Map to: Weak is observer IDENTITY, doesn't extend SPACE's TIME
 }
 
-fn exercise() {
+fn exercise() -> (usize, bool) {
     // TODO: Create a parent-child relationship where:
     // - Parent owns children (Rc<RefCell<Vec<Rc<Child>>>>)
     // - Children reference parent (Weak<Parent>)
     //
-    // This prevents a reference cycle that would leak memory.
+    // This prevents a reference cycle that would leak memory. The check
+    // counts allocations, so a leak shows up once exercise() returns.
+    //
+    // Return how many children the parent has, and whether every child can
+    // still reach the parent through its Weak.
     //
     // Hint: Define structs like:
     // struct Parent { children: RefCell<Vec<Rc<Child>>> }
@@ -43,7 +53,13 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, bool)>) -> Check<(usize, bool)> {
+    exercise
+        .returns("at least 2 children, each reaching its parent", |(children, reach)| *children >= 2 && *reach)
+        .frees_everything()
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
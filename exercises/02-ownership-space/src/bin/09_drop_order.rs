@@ -2,6 +2,8 @@
 //!
 //! SPACE ends when owner drops. But in what order?
 
+use check::Check;
+
 struct Named(&'static str);
 
 impl Drop for Named {
@@ -28,7 +30,8 @@ fn example() {
 fn exercise() {
     println!("\n=== Your Turn ===");
 
-    // TODO: Create a struct with multiple Named fields
+    // TODO: Create a struct with multiple Named fields, each named after its
+    //       field (`first: Named("first")`), and let it go out of scope
     // TODO: Predict the drop order before running
     // TODO: Verify your prediction
     //
@@ -45,7 +48,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.prints_in_order(&["Dropping: first", "Dropping: second", "Dropping: third"])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
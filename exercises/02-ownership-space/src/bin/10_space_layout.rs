@@ -2,6 +2,8 @@
 //!
 //! Observe how SPACE is laid out
 
+use check::Check;
+
 fn example() {
     let a: i32 = 1;
     let b: i32 = 2;
@@ -23,7 +25,7 @@ fn example() {
     // Vec is similar: the Vec struct is on stack, buffer is on heap
 }
 
-fn exercise() {
+fn exercise() -> [usize; 4] {
     // TODO: Create a nested structure and visualize its layout
     //
     // Try something like:
@@ -36,12 +38,24 @@ fn exercise() {
     // - The boxed field (the Box itself)
     // - The boxed contents (*boxed)
     //
-    // Draw a diagram of where each piece lives (stack vs heap)
+    // Draw a diagram of where each piece lives (stack vs heap), and return
+    // the four addresses in that order (`&outer as *const Outer as usize`)
+    //
+    // Then check it: put #[derive(spelled::SpaceReport)] on Outer and print
+    // outer.space_report(). It shows each field's offset, size and padding,
+    // and the heap SPACE the Box owns.
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<[usize; 4]>) -> Check<[usize; 4]> {
+    exercise.returns("inner and boxed inside Outer, *boxed far away on the heap", |[outer, inner, boxed, contents]| {
+        let within = |field: usize| field >= *outer && field - outer < 64;
+        within(*inner) && within(*boxed) && contents.abs_diff(*outer) > 4096
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -4,6 +4,8 @@
 
 use std::cell::Cell;
 
+use check::Check;
+
 fn example() {
     let x = Cell::new(5);
     let r1 = &x;
@@ -19,10 +21,11 @@ fn example() {
     // No IDENTITY into contents = no aliasing problem
 }
 
-fn exercise() {
-    // TODO: Create a Cell<i32>
+fn exercise() -> i32 {
+    // TODO: Create a Cell<i32> holding 0
     // TODO: Create multiple shared references to it
-    // TODO: Mutate through different references
+    // TODO: Mutate through different references: set it to 1 through the
+    //       first, then add 10 through the second, and return the value
     //
     // Try to get a reference to the contents:
     // let inner: &i32 = ???  // Can you do this?
@@ -33,7 +36,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("11", |value| *value == 11)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -4,6 +4,8 @@
 
 use std::cell::Cell;
 
+use check::Check;
+
 fn example() {
     let x = Cell::new(5);      // i32 is Copy: works
     println!("x = {}", x.get());
@@ -15,22 +17,33 @@ fn example() {
     // This only works for Copy types
 }
 
-fn exercise() {
-    // TODO: Try to create a Cell<Vec<i32>> and call .get()
-    //
-    // let y = Cell::new(vec![1, 2, 3]);
-    // let v = y.get();  // What error do you get?
+fn exercise() -> (Vec<i32>, Vec<i32>) {
+    // TODO: Try calling .get() on a Cell<Vec<i32>>: uncomment it in
+    // compile_fail() below. What error do you get?
     //
     // Map to: Cell avoids aliasing by copying. Can't copy non-Copy.
     // For non-Copy types, use RefCell instead.
     //
     // Question: What method CAN you use on Cell<Vec<i32>>?
     // Hint: try .take() or .replace()
+    //
+    // Then take the Vec out of y and return it, together with what y holds
+    // afterwards: (taken, left)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let y = Cell::new(vec![1, 2, 3]);
+    // let v = y.get();  //~ E0599
+}
+
+fn check(exercise: Check<(Vec<i32>, Vec<i32>)>) -> Check<(Vec<i32>, Vec<i32>)> {
+    exercise.returns("([1, 2, 3], [])", |(taken, left)| *taken == [1, 2, 3] && left.is_empty())
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
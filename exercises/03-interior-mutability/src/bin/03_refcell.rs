@@ -4,6 +4,8 @@
 
 use std::cell::RefCell;
 
+use check::Check;
+
 fn example() {
     let x = RefCell::new(vec![1, 2, 3]);
 
@@ -39,7 +41,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.panics("already borrowed")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -4,6 +4,8 @@
 
 use std::cell::RefCell;
 
+use check::Check;
+
 fn example() {
     let x = RefCell::new(5);
 
@@ -23,7 +25,7 @@ fn example() {
     // Map to: the guard's existence = IDENTITY's TIME span
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     let x = RefCell::new(vec![1, 2, 3]);
 
     // TODO: Demonstrate that dropping a guard ends the borrow
@@ -31,7 +33,8 @@ fn exercise() {
     // 1. Take a borrow()
     // 2. Drop it explicitly
     // 3. Take a borrow_mut()
-    // 4. Modify the vector
+    // 4. Modify the vector: push 4
+    // 5. Return it (x.into_inner())
     //
     // Question: What happens if you forget to drop the shared borrow?
 
@@ -39,7 +42,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("[1, 2, 3, 4]", |v| *v == [1, 2, 3, 4])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
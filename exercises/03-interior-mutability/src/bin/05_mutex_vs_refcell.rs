@@ -6,6 +6,8 @@
 use std::cell::RefCell;
 use std::sync::Mutex;
 
+use check::Check;
+
 fn example() {
     let refcell = RefCell::new(5);
     let mutex = Mutex::new(5);
@@ -19,23 +21,34 @@ fn example() {
     // Key difference: RefCell panics, Mutex blocks
 }
 
-fn exercise() {
-    // TODO: Try to send RefCell to another thread
-    //
-    // use std::thread;
-    // let refcell = RefCell::new(5);
-    // let r = &refcell;
-    // thread::spawn(move || { println!("{}", r.borrow()); });
+fn exercise() -> i32 {
+    // TODO: Try to send RefCell to another thread: uncomment the spawn in
+    // compile_fail() below
     //
     // What error do you get?
     // Map to: RefCell is not Sync. Mutex is Sync.
     //
     // Question: Why is RefCell not thread-safe?
+    //
+    // Then do it here with a Mutex holding 5: add 1 from a scoped thread,
+    // and return what the Mutex holds afterwards (6)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    use std::thread;
+    let refcell = RefCell::new(5);
+    let r = &refcell;
+    // thread::spawn(move || { println!("{}", r.borrow()); });  //~ E0277
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |x| *x == 6)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
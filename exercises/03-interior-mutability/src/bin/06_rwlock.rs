@@ -4,6 +4,8 @@
 
 use std::sync::RwLock;
 
+use check::Check;
+
 fn example() {
     let x = RwLock::new(5);
 
@@ -26,10 +28,11 @@ fn example() {
     // Map to: same as &T / &mut T, enforced at runtime across threads
 }
 
-fn exercise() {
-    // TODO: Create an RwLock<Vec<i32>>
-    // TODO: Spawn multiple reader threads
-    // TODO: Have one writer thread modify the data
+fn exercise() -> (Vec<Vec<i32>>, Vec<i32>) {
+    // TODO: Create an RwLock<Vec<i32>> holding [1, 2, 3]
+    // TODO: Spawn multiple reader threads, each cloning what it reads
+    // TODO: Have one writer thread modify the data: push 4
+    // TODO: Return what each reader saw, and the data at the end
     //
     // Questions:
     // - What happens if a reader holds the lock while writer tries to write?
@@ -38,7 +41,13 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(Vec<Vec<i32>>, Vec<i32>)>) -> Check<(Vec<Vec<i32>>, Vec<i32>)> {
+    exercise.returns("[1, 2, 3, 4] at the end, each reader seeing all of the writer's push or none of it", |(reads, last)| {
+        !reads.is_empty() && reads.iter().all(|read| *read == [1, 2, 3] || *read == [1, 2, 3, 4]) && *last == [1, 2, 3, 4]
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
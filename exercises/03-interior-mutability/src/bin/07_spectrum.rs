@@ -4,6 +4,8 @@
 
 use std::cell::{Cell, RefCell, UnsafeCell};
 
+use check::Check;
+
 fn example() {
     // Compile-time: borrow checker
     let mut x = 5;
@@ -43,11 +45,18 @@ fn exercise() {
     // | Cell<T>    | ???                  | ???                | ???            |
     // | RefCell<T> | ???                  | ???                | Runtime panic  |
     // | UnsafeCell | ???                  | None               | ???            |
+    //
+    // TODO: Then run into the RefCell row's risk: call borrow_mut() while a
+    //       borrow() is still alive
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.panics("already borrowed")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -4,6 +4,8 @@
 
 use std::cell::RefCell;
 
+use check::Check;
+
 fn example() {
     // Runtime equivalent with RefCell:
     let x = RefCell::new(5);
@@ -25,10 +27,8 @@ fn example() {
 fn exercise() {
     // TODO: Write both versions side-by-side
     //
-    // Compile-time version (this won't compile):
-    // let mut x = 5;
-    // let r = &x;
-    // let m = &mut x;  // ERROR at compile time
+    // Compile-time version (this won't compile): uncomment it in
+    // compile_fail() below
     //
     // Runtime version:
     // let x = RefCell::new(5);
@@ -40,7 +40,20 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let mut x = 5;
+    let r = &x;
+    // let m = &mut x;  //~ E0502
+    // *m += 1;
+    println!("{}", r);
+}
+
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise.panics("already borrowed")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! The borrow checker rejects some valid programs
 
+use check::Check;
+
 fn example() {
     let mut v = vec![1, 2, 3, 4, 5];
 
@@ -22,21 +24,32 @@ fn example() {
     // It uses unsafe inside to create two non-overlapping &mut
 }
 
-fn exercise() {
-    // TODO: Try the "doesn't work" version - see the error
-    //
-    // let mut v = vec![1, 2, 3, 4, 5];
-    // let a = &mut v[0];
-    // let b = &mut v[4];  // What error do you get?
+fn exercise() -> Vec<i32> {
+    // TODO: Uncomment the "doesn't work" version in compile_fail() below and
+    // see the error
     //
-    // Then fix it using split_at_mut or indices
+    // Then fix it here, using split_at_mut or indices: set v[0] to 10 and
+    // v[4] to 50 through a and b, and return v
     //
     // Question: Why can't the borrow checker prove v[0] and v[4] are disjoint?
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let mut v = vec![1, 2, 3, 4, 5];
+    // let a = &mut v[0];
+    // let b = &mut v[4];  //~ E0499
+    // *a = 10;
+    // *b = 50;
+}
+
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("[10, 2, 3, 4, 50]", |v| *v == [10, 2, 3, 4, 50])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
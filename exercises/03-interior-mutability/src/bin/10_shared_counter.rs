@@ -7,6 +7,8 @@ use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use check::Check;
+
 fn example() {
     // Version 1: Single-thread, Cell (simplest)
     let counter1 = Cell::new(0u64);
@@ -31,13 +33,13 @@ fn example() {
     // Map each to: IDENTITY sharing, TIME synchronization, Cost
 }
 
-fn exercise() {
+fn exercise() -> u64 {
     // TODO: Implement a shared counter that multiple threads can increment
     //
     // 1. Use Arc<AtomicU64>
     // 2. Spawn 10 threads
     // 3. Each thread increments 1000 times
-    // 4. Print the final count (should be 10000)
+    // 4. Print the final count (should be 10000) and return it
     //
     // Questions:
     // - Why is AtomicU64 better than Arc<Mutex<u64>> for this?
@@ -46,7 +48,13 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<u64>) -> Check<u64> {
+    exercise
+        .prints("10000")
+        .returns("10000", |count| *count == 10000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
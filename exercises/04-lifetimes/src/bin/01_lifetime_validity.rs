@@ -2,6 +2,8 @@
 //!
 //! 'a is not memory duration. It's how long IDENTITY is valid.
 
+use check::Check;
+
 fn pass_through<'a>(x: &'a i32) -> &'a i32 {
     x  // Returned IDENTITY valid for same TIME span as input
 }
@@ -21,22 +23,33 @@ fn example() {
     // Map to: IDENTITY can't outlive the SPACE it points to
 }
 
-fn exercise() {
-    // TODO: Create a situation where a reference outlives its data
-    //
-    // let result;
-    // {
-    //     let inner = 10;
-    //     result = &inner;  // What error do you get?
-    // }
-    // println!("{}", result);
+fn exercise() -> i32 {
+    // TODO: Uncomment compile_fail() below: a reference that outlives its data
     //
+    // What error do you get?
     // Map to: IDENTITY validity must not exceed SPACE duration
+    //
+    // Then fix it here: give inner the same scope as result, and return what
+    // result points to (10)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    // let result;
+    {
+        let inner = 10;
+        // result = &inner;  //~ E0597
+    }
+    // println!("{}", result);
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("10", |result| *result == 10)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! When returning IDENTITY from multiple inputs, which TIME span applies?
 
+use check::Check;
+
 fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
     if x.len() > y.len() { x } else { y }
 }
@@ -18,24 +20,36 @@ fn example() {
     // Map to: result's IDENTITY validity = intersection of input validities
 }
 
-fn exercise() {
+fn exercise() -> String {
+    // TODO: Uncomment the last line of compile_fail() below to see the error
+    //
+    // Why does this fail even though result might point to s1? And why is it
+    // reported where longest is called, not where result is used?
+    // Map to: compiler doesn't know which branch was taken
+    //
+    // Then fix it here: let s2 live as long as result, and return result as
+    // a String ("long string")
+
+    todo!("Exercise incomplete");
+}
+
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
     let s1 = String::from("long string");
     let result;
     {
         let s2 = String::from("short");
-        result = longest(&s1, &s2);
+        result = longest(&s1, &s2);  //~ E0597
         println!("Inside: {}", result);
     }
-    // TODO: Uncomment to see the error:
     // println!("Outside: {}", result);
-    //
-    // Why does this fail even though result might point to s1?
-    // Map to: compiler doesn't know which branch was taken
+}
 
-    todo!("Exercise incomplete");
+fn check(exercise: Check<String>) -> Check<String> {
+    exercise.returns("\"long string\"", |longest| longest == "long string")
 }
 
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
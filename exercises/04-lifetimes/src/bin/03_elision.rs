@@ -2,6 +2,8 @@
 //!
 //! These are the same:
 
+use check::Check;
+
 fn explicit<'a>(x: &'a str) -> &'a str { x }
 fn elided(x: &str) -> &str { x }
 
@@ -17,20 +19,30 @@ fn example() {
     // 3. If &self, output gets self's lifetime
 }
 
-fn exercise() {
-    // TODO: Write a function where elision doesn't work
+fn exercise() -> &'static str {
+    // TODO: Uncomment broken() in compile_fail() below: a function where
+    // elision doesn't work. What error do you get?
     //
-    // fn broken(x: &str, y: &str) -> &str {
-    //     if x.len() > y.len() { x } else { y }
-    // }
+    // Fix it here by adding explicit lifetimes, and return what the fixed
+    // version gives for "long string" and "short"
     //
-    // What error do you get? Fix it by adding explicit lifetimes.
     // Why can't elision figure this out?
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    // fn broken(x: &str, y: &str) -> &str {  //~ E0106
+    //     if x.len() > y.len() { x } else { y }
+    // }
+}
+
+fn check(exercise: Check<&'static str>) -> Check<&'static str> {
+    exercise.returns("\"long string\"", |longest| *longest == "long string")
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! A struct holding a reference: the struct's TIME is bounded by the reference's
 
+use check::Check;
+
 struct Holder<'a> {
     value: &'a i32,
 }
@@ -15,22 +17,33 @@ fn example() {
     // Map to: struct's TIME <= contained IDENTITY's TIME
 }
 
-fn exercise() {
-    // TODO: Create a situation where holder outlives the referenced data
-    //
-    // let holder;
-    // {
-    //     let y = 10;
-    //     holder = Holder { value: &y };
-    // }
-    // println!("{}", holder.value);  // What error?
+fn exercise() -> i32 {
+    // TODO: Uncomment compile_fail() below: holder outlives the referenced data
     //
+    // What error do you get?
     // Map to: struct containing IDENTITY must not outlive the SPACE
+    //
+    // Then fix it here: let y live as long as holder, and return the value
+    // holder points to (10)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    // let holder;
+    {
+        let y = 10;
+        // holder = Holder { value: &y };  //~ E0597
+    }
+    // println!("{}", holder.value);
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("10", |value| *value == 10)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! 'static means: IDENTITY valid for entire program TIME
 
+use check::Check;
+
 fn example() {
     let s: &'static str = "hello";  // String literal: lives in binary
     println!("Static string: {}", s);
@@ -14,21 +16,32 @@ fn example() {
     println!("Leaked value: {}", leaked);
 }
 
-fn exercise() {
-    // TODO: Try to create &'static to a local variable
-    //
-    // let x = 5;
-    // let r: &'static i32 = &x;  // What error?
+fn exercise() -> &'static i32 {
+    // TODO: Try to create &'static to a local variable: uncomment the line in
+    // compile_fail() below. What error do you get?
     //
     // Local variables don't live for the entire program.
     // 'static requires the SPACE to exist forever.
     //
     // Question: When would you use Box::leak?
+    //
+    // Then get a &'static i32 to 5 the way that works, with Box::leak, and
+    // return it
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let x = 5;
+    // let r: &'static i32 = &x;  //~ E0597
+}
+
+fn check(exercise: Check<&'static i32>) -> Check<&'static i32> {
+    exercise.returns("a &'static to 5", |leaked| **leaked == 5)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! Non-Lexical Lifetimes: IDENTITY validity ends at last use, not scope end
 
+use check::Check;
+
 fn example() {
     let mut x = 5;
 
@@ -16,23 +18,33 @@ fn example() {
     // Map to: TIME span = first use to last use, not lexical scope
 }
 
-fn exercise() {
-    let mut x = 5;
-
-    // TODO: Move the println!(r) after the &mut x. What happens?
-    //
-    // let r = &x;
-    // let m = &mut x;
-    // println!("{}", r);  // Error: shared borrow still in use
+fn exercise() -> i32 {
+    // TODO: In compile_fail() below, move the println!(r) after the &mut x.
+    // What happens?
     //
     // The borrow checker sees r is used after m is created.
     // That violates: !(shared IDENTITY && mutation)
+    //
+    // Then write the version NLL accepts here: print r, add 1 through m,
+    // and return x (6)
 
-    let _ = x;
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let mut x = 5;
+    let r = &x;
+    println!("{}", r);
+    let m = &mut x;  //~ E0502
+    *m += 1;
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |x| *x == 6)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! The borrow checker tracks IDENTITY, not values
 
+use check::Check;
+
 fn example() {
     let mut v = vec![1, 2, 3];
 
@@ -21,15 +23,14 @@ fn example() {
     // Map to: decidability. Value tracking is undecidable in general.
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     let mut v = vec![1, 2, 3, 4, 5];
 
-    // TODO: Try the "doesn't work" version:
-    // let r0 = &mut v[0];
-    // let r1 = &mut v[4];
+    // TODO: Try the "doesn't work" version: uncomment it in compile_fail()
+    // below. What error do you get?
     //
-    // What error do you get?
-    // Fix it using split_at_mut.
+    // Then fix it here using split_at_mut: add 10 through both r0 and r1,
+    // and return v
     //
     // Question: Why can't the compiler prove v[0] and v[4] don't overlap?
 
@@ -37,7 +38,20 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    let mut v = vec![1, 2, 3, 4, 5];
+    // let r0 = &mut v[0];
+    // let r1 = &mut v[4];  //~ E0499
+    // *r0 += 10;
+    // *r1 += 10;
+}
+
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("[11, 2, 3, 4, 15]", |v| *v == [11, 2, 3, 4, 15])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
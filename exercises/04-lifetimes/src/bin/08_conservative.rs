@@ -2,6 +2,8 @@
 //!
 //! It rejects some valid programs
 
+use check::Check;
+
 fn example() {
     let mut x = 5;
     let r = &mut x;
@@ -21,7 +23,7 @@ fn example() {
     // Sound but incomplete: no false negatives, some false positives
 }
 
-fn exercise() {
+fn exercise() -> i32 {
     let mut x = 5;
     let r = &mut x;
 
@@ -34,12 +36,18 @@ fn exercise() {
     // let s = &x;  // Does this work?
     //
     // Map to: NLL makes the borrow checker less conservative
+    //
+    // Once it works: write 6 through r first, then return what s reads
 
     let _ = r;
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("6", |value| *value == 6)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
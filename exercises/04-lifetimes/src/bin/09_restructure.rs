@@ -2,6 +2,8 @@
 //!
 //! Often the fix is restructuring, not interior mutability
 
+use check::Check;
+
 struct Data {
     a: i32,
     b: i32,
@@ -22,24 +24,37 @@ fn example() {
     // Map to: direct field access lets borrow checker see disjointness
 }
 
-fn exercise() {
-    // TODO: Try accessing through methods instead
-    //
-    // impl Data {
-    //     fn get_a(&mut self) -> &mut i32 { &mut self.a }
-    //     fn get_b(&mut self) -> &mut i32 { &mut self.b }
-    // }
-    //
-    // let a = data.get_a();
-    // let b = data.get_b();  // What error?
+fn exercise() -> i32 {
+    // TODO: Try accessing through methods instead: uncomment the last three
+    // lines of compile_fail() below. What error do you get?
     //
     // Why does direct field access work but methods don't?
     // Map to: method calls hide the disjointness from the compiler
+    //
+    // Then fix it here with direct field borrows: add b to a on
+    // Data { a: 1, b: 2 }, and return a (3)
 
     todo!("Exercise incomplete");
 }
 
+#[cfg(feature = "compile-fail")]
+fn compile_fail() {
+    impl Data {
+        fn get_a(&mut self) -> &mut i32 { &mut self.a }
+        fn get_b(&mut self) -> &mut i32 { &mut self.b }
+    }
+
+    let mut data = Data { a: 1, b: 2 };
+    // let a = data.get_a();
+    // let b = data.get_b();  //~ E0499
+    // *a += *b;
+}
+
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("3", |a| *a == 3)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! Sometimes you know more than the compiler
 
+use check::Check;
+
 fn example() {
     let mut v = vec![1, 2, 3, 4, 5];
 
@@ -24,7 +26,7 @@ fn example() {
     // Compiler can't prove it, human can
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     // TODO: Think about when unsafe is appropriate
     //
     // Unsafe is appropriate when:
@@ -38,11 +40,20 @@ fn exercise() {
     // 3. There's a safe alternative
     //
     // Question: What invariant does split_at_mut rely on?
+    //
+    // TODO: Write your own split_at_mut with as_mut_ptr and
+    //       slice::from_raw_parts_mut, stating the invariant in a SAFETY
+    //       comment. Split vec![1, 2, 3, 4, 5] at 2, set left[0] = 10 and
+    //       right[0] = 20, and return the Vec
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("[10, 2, 20, 4, 5]", |v| *v == [10, 2, 20, 4, 5])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::thread;
 
+use check::Check;
+
 static X: AtomicI32 = AtomicI32::new(0);
 
 fn example() {
@@ -25,9 +27,9 @@ fn example() {
     // Might be 0 or 42 - Relaxed gives no visibility guarantees
 }
 
-fn exercise() {
+fn exercise() -> (usize, usize) {
     // TODO: Run the example many times in a loop
-    // TODO: Count how often you see 0 vs 42
+    // TODO: Count how often you see 0 vs 42, and return the two counts
     //
     // for _ in 0..1000 {
     //     // spawn writer and reader
@@ -40,7 +42,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, usize)>) -> Check<(usize, usize)> {
+    exercise.returns("two counts adding up to 1000 runs", |(zeros, answers)| zeros + answers == 1000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::thread;
 
+use check::Check;
+
 static DATA: AtomicI32 = AtomicI32::new(0);
 static FLAG: AtomicBool = AtomicBool::new(false);
 
@@ -31,8 +33,9 @@ fn example() {
     // Might not be 42! FLAG may be visible before DATA (reordering)
 }
 
-fn exercise() {
+fn exercise() -> (usize, usize) {
     // TODO: Run many iterations and check for anomalies
+    // TODO: Return how many iterations read 42 and how many saw an anomaly
     //
     // for i in 0..1000 {
     //     // reset DATA and FLAG
@@ -48,7 +51,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, usize)>) -> Check<(usize, usize)> {
+    exercise.returns("two counts adding up to 1000 iterations", |(clean, anomalies)| clean + anomalies == 1000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
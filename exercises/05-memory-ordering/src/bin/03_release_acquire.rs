@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::thread;
 
+use check::Check;
+
 static DATA: AtomicI32 = AtomicI32::new(0);
 static FLAG: AtomicBool = AtomicBool::new(false);
 
@@ -32,11 +34,11 @@ fn example() {
     // Map to: happens-before edge between TIME lines
 }
 
-fn exercise() {
+fn exercise() -> (usize, usize) {
     // TODO: Verify that Release/Acquire fixes the visibility problem
     //
     // Run 1000 iterations with Release/Acquire
-    // Verify DATA is always 42
+    // Verify DATA is always 42: return (iterations, anomalies)
     //
     // Then change back to Relaxed and see if anomalies return
     //
@@ -45,7 +47,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, usize)>) -> Check<(usize, usize)> {
+    exercise.returns("1000 iterations without an anomaly", |result| *result == (1000, 0))
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
+use check::Check;
+
 static A: AtomicBool = AtomicBool::new(false);
 static B: AtomicBool = AtomicBool::new(false);
 
@@ -43,9 +45,11 @@ fn example() {
     // With SeqCst: if T3 sees (true, false), T4 cannot see (true, false)
 }
 
-fn exercise() {
+fn exercise() -> (usize, usize) {
     // TODO: Change SeqCst to Acquire/Release and run many times
     // TODO: Can you observe both threads seeing (true, false)?
+    // TODO: Over 1000 runs, count the runs where both did and the runs where
+    //       they didn't, and return the two counts
     //
     // SeqCst provides a total order all threads agree on
     // Acquire/Release only provides pairwise ordering
@@ -55,7 +59,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(usize, usize)>) -> Check<(usize, usize)> {
+    exercise.returns("two counts adding up to 1000 runs", |(both, other)| both + other == 1000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
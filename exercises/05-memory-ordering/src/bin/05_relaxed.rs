@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread;
 
+use check::Check;
+
 static COUNTER: AtomicU64 = AtomicU64::new(0);
 
 fn example() {
@@ -28,10 +30,12 @@ fn example() {
     // Each increment is atomic (no lost updates)
 }
 
-fn exercise() {
+fn exercise() -> u64 {
     // TODO: Implement a counter where intermediate values matter
     //
     // For example: thread A increments, thread B reads and acts on value
+    // Have A count to 1000 while B reads until it sees 1000, and return the
+    // last value B read
     // Does Relaxed still work? When would you need stronger ordering?
     //
     // Question: What does "atomic" mean without ordering guarantees?
@@ -39,7 +43,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<u64>) -> Check<u64> {
+    exercise.returns("1000", |last| *last == 1000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
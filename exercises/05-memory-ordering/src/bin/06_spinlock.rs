@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
+use check::Check;
+
 static LOCK: AtomicBool = AtomicBool::new(false);
 static mut DATA: i32 = 0;
 
@@ -43,11 +45,12 @@ fn example() {
     unsafe { println!("DATA = {}", DATA); }  // Always 10000
 }
 
-fn exercise() {
+fn exercise() -> i32 {
     // TODO: What happens if you change Acquire to Relaxed in the lock?
     // TODO: What happens if you change Release to Relaxed in the unlock?
     //
-    // Try it and reason about why it breaks (or doesn't)
+    // Try it and reason about why it breaks (or doesn't): run the example's
+    // 10 threads of 1000 increments through your with_lock, and return DATA
     //
     // Map to: Acquire on lock sees previous critical section's writes
     //         Release on unlock publishes this critical section's writes
@@ -55,7 +58,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<i32>) -> Check<i32> {
+    exercise.returns("10000", |data| *data == 10000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -7,6 +7,8 @@ use std::sync::Mutex;
 use std::ptr;
 use std::thread;
 
+use check::Check;
+
 static INSTANCE: AtomicPtr<String> = AtomicPtr::new(ptr::null_mut());
 static INIT_LOCK: Mutex<()> = Mutex::new(());
 
@@ -41,7 +43,7 @@ fn example() {
     // Release publishes initialized data, Acquire sees it
 }
 
-fn exercise() {
+fn exercise() -> usize {
     // TODO: What would happen with Relaxed instead of Release on store?
     // TODO: What would happen with Relaxed instead of Acquire on load?
     //
@@ -49,11 +51,18 @@ fn exercise() {
     // initialized String data. This is a classic double-checked locking bug.
     //
     // Question: Why is the second load (under lock) Relaxed?
+    //
+    // TODO: Call get_instance() from 10 threads, and return how many distinct
+    //       addresses they got back
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<usize>) -> Check<usize> {
+    exercise.returns("1 distinct instance", |instances| *instances == 1)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
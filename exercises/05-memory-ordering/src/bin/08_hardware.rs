@@ -2,6 +2,8 @@
 //!
 //! Memory ordering exists because hardware defers coherence
 
+use check::Check;
+
 fn example() {
     println!("Memory ordering abstracts over hardware differences:");
     println!();
@@ -37,7 +39,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
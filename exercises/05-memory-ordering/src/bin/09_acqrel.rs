@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::thread;
 
+use check::Check;
+
 static X: AtomicI32 = AtomicI32::new(0);
 
 fn example() {
@@ -27,7 +29,7 @@ fn example() {
     // AcqRel = Acquire on the read + Release on the write
 }
 
-fn exercise() {
+fn exercise() -> u64 {
     // TODO: When to use AcqRel vs SeqCst?
     //
     // AcqRel: pairwise ordering between specific threads
@@ -36,13 +38,20 @@ fn exercise() {
     // Try: implement a simple ticket lock using fetch_add
     // Does it need AcqRel or SeqCst?
     //
+    // Test it: 4 threads each take the lock 1000 times and increment a
+    // plain (non-atomic) counter inside it. Return the counter.
+    //
     // static TICKET: AtomicUsize = AtomicUsize::new(0);
     // static SERVING: AtomicUsize = AtomicUsize::new(0);
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<u64>) -> Check<u64> {
+    exercise.returns("4000", |count| *count == 4000)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
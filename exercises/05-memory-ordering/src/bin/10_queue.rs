@@ -6,6 +6,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::cell::UnsafeCell;
 use std::thread;
 
+use check::Check;
+
 const SIZE: usize = 16;
 
 struct SpscQueue<T> {
@@ -83,20 +85,27 @@ fn example() {
     consumer.join().unwrap();
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     // TODO: Analyze the ordering choices in SpscQueue
     //
     // 1. Why is head.load Relaxed in push but tail.load Acquire?
     // 2. Why is head.store Release?
     // 3. What would break if we used Relaxed everywhere?
     //
+    // TODO: Push 0..10 from a producer thread, pop them in a consumer thread,
+    //       and return what the consumer popped, in order
+    //
     // Map to: Release on write publishes the data
     //         Acquire on read sees the published data
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]", |popped| *popped == (0..10).collect::<Vec<_>>())
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
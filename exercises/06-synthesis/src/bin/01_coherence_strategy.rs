@@ -5,6 +5,8 @@
 use std::sync::{Arc, RwLock};
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use check::Check;
+
 struct Config {
     timeout_ms: u64,
     max_connections: u64,
@@ -35,9 +37,13 @@ fn example() {
     // Readers get a snapshot, never block
 }
 
-fn exercise() {
+fn exercise() -> Vec<Vec<u64>> {
     // TODO: Implement Option C (Arc swap) for the Config scenario
     //
+    // Test it: start at timeout_ms 1000 and have a writer publish 1001 to
+    // 1010, one new Config at a time, while reader threads record the
+    // timeout_ms of each snapshot they get. Return what each reader saw.
+    //
     // Hints:
     // - Use arc_swap crate, or implement with AtomicPtr
     // - Readers clone the Arc (cheap)
@@ -53,7 +59,15 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<Vec<u64>>>) -> Check<Vec<Vec<u64>>> {
+    exercise.returns("snapshots from 1000 to 1010 that never go back in time", |readers| {
+        !readers.is_empty() && readers.iter().all(|seen| {
+            seen.iter().all(|timeout| (1000..=1010).contains(timeout)) && seen.windows(2).all(|pair| pair[0] <= pair[1])
+        })
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,12 @@
 use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 
+use check::Check;
+
+// Counts allocations, for the leak check in check()
+#[global_allocator]
+static ALLOCATOR: check::CountingAllocator = check::CountingAllocator;
+
 struct Node {
     value: i32,
     parent: RefCell<Weak<Node>>,      // Weak: doesn't keep parent alive
@@ -52,7 +58,7 @@ fn example() {
     // Watch the drop order: children first, then root
 }
 
-fn exercise() {
+fn exercise() -> (Vec<i32>, Vec<i32>) {
     // TODO: Create a doubly-linked list using Weak for prev pointers
     //
     // struct ListNode {
@@ -62,15 +68,21 @@ fn exercise() {
     // }
     //
     // Build a list: 1 <-> 2 <-> 3
-    // Navigate forward and backward
-    // Verify proper cleanup (no memory leaks)
+    // Navigate forward and backward, and return the values in both orders
+    // Verify proper cleanup (no memory leaks): the check counts allocations
     //
     // Question: What would happen if prev used Rc instead of Weak?
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(Vec<i32>, Vec<i32>)>) -> Check<(Vec<i32>, Vec<i32>)> {
+    exercise
+        .returns("([1, 2, 3], [3, 2, 1])", |(forward, backward)| *forward == [1, 2, 3] && *backward == [3, 2, 1])
+        .frees_everything()
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -5,6 +5,8 @@
 use std::sync::mpsc;
 use std::thread;
 
+use check::Check;
+
 type Job = Box<dyn FnOnce() + Send + 'static>;
 
 struct ThreadPool {
@@ -62,7 +64,7 @@ fn example() {
     // Jobs must be 'static (no borrowed IDENTITY that might expire)
 }
 
-fn exercise() {
+fn exercise() -> usize {
     // TODO: Add graceful shutdown to ThreadPool
     //
     // Hints:
@@ -70,12 +72,19 @@ fn exercise() {
     // - Send a shutdown signal (e.g., Option<Job> where None = shutdown)
     // - impl Drop for ThreadPool that joins all workers
     //
+    // Test it: submit 8 jobs that each increment a shared counter, drop the
+    // pool without sleeping, and return the counter
+    //
     // Question: Why must jobs be 'static? What would break with borrowed data?
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<usize>) -> Check<usize> {
+    exercise.returns("8: every job ran before the pool was gone", |jobs| *jobs == 8)
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -3,6 +3,8 @@
 use std::cell::{Cell, RefCell, OnceCell};
 use std::sync::{Arc, Mutex, OnceLock};
 
+use check::Check;
+
 fn example() {
     // Scenario 1: Counter incremented by callback, single thread
     let counter1 = Cell::new(0u64);
@@ -31,7 +33,7 @@ fn example() {
     println!("OnceLock: {}", CONFIG.get().unwrap());
 }
 
-fn exercise() {
+fn exercise() -> (Option<String>, Option<String>) {
     // TODO: For each scenario, explain WHY that type was chosen
     //
     // Fill in the decision matrix:
@@ -42,14 +44,29 @@ fn exercise() {
     // | Shared counter        | ???      | Multi        | ???   | ???      |
     // | Global init           | ???      | Multi        | ???   | ???      |
     //
+    // Check the matrix against the compiler. Single/Multi is Send and Sync,
+    // Copy? is Copy:
+    //
+    // spelled::properties!(Cell<u64>);
+    // spelled::properties!(counter1);
+    //
     // Then implement: a RefCell-based cache that can be updated (not just once)
     //
     // let cache: RefCell<Option<String>> = RefCell::new(None);
+    //
+    // Store "first", then update it to "second"; return what the cache held
+    // after each
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(Option<String>, Option<String>)>) -> Check<(Option<String>, Option<String>)> {
+    exercise.returns("(Some(\"first\"), Some(\"second\"))", |(before, after)| {
+        before.as_deref() == Some("first") && after.as_deref() == Some("second")
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
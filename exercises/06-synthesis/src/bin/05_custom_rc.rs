@@ -6,6 +6,12 @@ use std::cell::Cell;
 use std::ops::Deref;
 use std::ptr::NonNull;
 
+use check::Check;
+
+// Counts allocations, for the leak check in check()
+#[global_allocator]
+static ALLOCATOR: check::CountingAllocator = check::CountingAllocator;
+
 struct MyRc<T> {
     ptr: NonNull<Inner<T>>,
 }
@@ -78,7 +84,7 @@ fn example() {
     // When a drops, count reaches 0, Inner deallocated
 }
 
-fn exercise() {
+fn exercise() -> (Option<i32>, Option<i32>) {
     // TODO: Add a weak_count and implement MyWeak<T>
     //
     // struct Inner<T> {
@@ -92,12 +98,22 @@ fn exercise() {
     // - Return Option<MyRc<T>> on upgrade()
     // - Only deallocate Inner when both counts are 0
     //
+    // Test it on MyRc::new(42): return what upgrade() gives while the MyRc is
+    // alive, and after it is dropped. The check counts allocations, so
+    // Inner must be freed by then too.
+    //
     // Question: Why do we need separate strong and weak counts?
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(Option<i32>, Option<i32>)>) -> Check<(Option<i32>, Option<i32>)> {
+    exercise
+        .returns("(Some(42), None)", |upgraded| *upgraded == (Some(42), None))
+        .frees_everything()
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
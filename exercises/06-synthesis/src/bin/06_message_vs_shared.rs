@@ -7,6 +7,8 @@ use std::sync::{Arc, Mutex};
 use std::collections::VecDeque;
 use std::thread;
 
+use check::Check;
+
 fn message_passing() {
     println!("=== Message Passing ===");
     let (tx, rx) = mpsc::channel();
@@ -57,7 +59,7 @@ fn example() {
     // Shared state: coherence via TIME serialization (Mutex)
 }
 
-fn exercise() {
+fn exercise() -> (Vec<i32>, Vec<i32>) {
     // TODO: Implement a producer-consumer with bounded queue
     //
     // Requirements:
@@ -69,12 +71,26 @@ fn exercise() {
     // 1. Using channels (mpsc::sync_channel)
     // 2. Using shared state (Mutex + Condvar)
     //
+    // Produce 0 to 19 with each, and return what the consumers received:
+    // (channel, shared state)
+    //
     // Question: Which is simpler? Which is more flexible?
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<(Vec<i32>, Vec<i32>)>) -> Check<(Vec<i32>, Vec<i32>)> {
+    exercise.returns("0 to 19 from both, in some order", |(channel, shared)| {
+        let all = |received: &Vec<i32>| {
+            let mut sorted = received.clone();
+            sorted.sort();
+            sorted == (0..20).collect::<Vec<i32>>()
+        };
+        all(channel) && all(shared)
+    })
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
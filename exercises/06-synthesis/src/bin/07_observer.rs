@@ -4,6 +4,8 @@
 
 use std::rc::{Rc, Weak};
 
+use check::Check;
+
 trait Observer {
     fn on_change(&self, value: i32);
 }
@@ -68,7 +70,7 @@ fn example() {
     // Weak allows IDENTITY without extending SPACE lifetime
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     // TODO: Make the observer pattern thread-safe
     //
     // Changes needed:
@@ -76,13 +78,20 @@ fn exercise() {
     // - Arc<Mutex<Vec<...>>> for observers list
     // - Or use RwLock for better read performance
     //
+    // Test it: from a spawned thread, set the value to 10 and then 20, and
+    // return the values an observer recorded
+    //
     // Question: What happens if an observer's on_change
     // calls set_value? How do you prevent deadlock?
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("[10, 20]", |seen| *seen == [10, 20])
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
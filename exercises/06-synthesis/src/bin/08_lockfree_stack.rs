@@ -5,6 +5,8 @@
 use std::sync::atomic::{AtomicPtr, Ordering};
 use std::ptr;
 
+use check::Check;
+
 struct Node<T> {
     value: T,
     next: *mut Node<T>,
@@ -78,7 +80,7 @@ fn example() {
     // Note: This has the ABA problem!
 }
 
-fn exercise() {
+fn exercise() -> Vec<i32> {
     // TODO: Research and explain the ABA problem
     //
     // The ABA problem:
@@ -93,11 +95,18 @@ fn exercise() {
     // - Tagged pointers (version counter)
     //
     // Question: Why doesn't this bug show up in single-threaded use?
+    //
+    // TODO: Push 0..1000 onto one Stack from 4 threads, 250 values each; once
+    //       they are joined, pop everything and return the values sorted
 
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<Vec<i32>>) -> Check<Vec<i32>> {
+    exercise.returns("0 to 999, each popped once", |popped| *popped == (0..1000).collect::<Vec<_>>())
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
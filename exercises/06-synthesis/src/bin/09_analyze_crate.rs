@@ -2,6 +2,8 @@
 //!
 //! Pick a crate and analyze its design
 
+use check::Check;
+
 fn example() {
     println!("Suggested crates to analyze:");
     println!();
@@ -35,7 +37,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -2,6 +2,8 @@
 //!
 //! Create something that requires understanding the full triangle
 
+use check::Check;
+
 fn example() {
     println!("Design ideas:");
     println!();
@@ -37,7 +39,11 @@ fn exercise() {
     todo!("Exercise incomplete");
 }
 
+fn check(exercise: Check<()>) -> Check<()> {
+    exercise
+}
+
 fn main() {
     example();
-    exercise();
+    check::run(exercise, check);
 }
@@ -0,0 +1,225 @@
+//! What an exercise has to do to count as done.
+//!
+//! Each exercise's `main` hands its `exercise()` to [`run`] together with a
+//! `check` function that says, on a [`Check`], what it must print, what it
+//! must return, whether it must panic, and whether it must free the SPACE it
+//! allocates.
+//!
+//! ```ignore
+//! fn check(exercise: Check<u64>) -> Check<u64> {
+//!     exercise.prints("10000").returns("10000", |count| *count == 10000)
+//! }
+//!
+//! fn main() {
+//!     example();
+//!     check::run(exercise, check);
+//! }
+//! ```
+//!
+//! Values, panics and allocations are checked inside the exercise's own
+//! process, which exits with an error when one fails. What it prints is
+//! checked by the `learn` runner, which sees the output; see [`protocol`].
+//! Run on its own, the exercise reports those checks as not verified.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::any::Any;
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, process};
+
+pub mod protocol;
+
+use protocol::Line;
+
+type Invariant<T> = Box<dyn Fn(&T) -> bool>;
+
+/// The checks for one exercise, built up one call at a time. Returned as it
+/// was given, there is nothing to verify: the exercise is a question to
+/// think through.
+pub struct Check<T> {
+    prints: Vec<Vec<&'static str>>,
+    returns: Vec<(&'static str, Invariant<T>)>,
+    panics: Option<&'static str>,
+    frees: bool,
+}
+
+impl<T> Check<T> {
+    /// Some line `exercise()` prints contains `pattern`.
+    pub fn prints(mut self, pattern: &'static str) -> Self {
+        self.prints.push(vec![pattern]);
+        self
+    }
+
+    /// Lines containing each pattern are printed, in this order.
+    pub fn prints_in_order(mut self, patterns: &[&'static str]) -> Self {
+        self.prints.push(patterns.to_vec());
+        self
+    }
+
+    /// The value `exercise()` returns satisfies `invariant`. `expected` says
+    /// what that means, for the report: `returns {expected}`.
+    pub fn returns(mut self, expected: &'static str, invariant: impl Fn(&T) -> bool + 'static) -> Self {
+        self.returns.push((expected, Box::new(invariant)));
+        self
+    }
+
+    /// `exercise()` panics, with a message containing `message`.
+    pub fn panics(mut self, message: &'static str) -> Self {
+        self.panics = Some(message);
+        self
+    }
+
+    /// Everything allocated while `exercise()` runs is freed once it returns
+    /// and its value is dropped. Needs [`CountingAllocator`].
+    pub fn frees_everything(mut self) -> Self {
+        self.frees = true;
+        self
+    }
+}
+
+/// Run `exercise` and check it. `check` takes the exercise's return type
+/// from `exercise`, so its closures need no annotations.
+pub fn run<T: Debug>(exercise: fn() -> T, check: fn(Check<T>) -> Check<T>) {
+    let check = check(Check { prints: Vec::new(), returns: Vec::new(), panics: None, frees: false });
+    let learn = env::var_os(protocol::ENV).is_some();
+    if learn {
+        println!("{}", Line::Begin);
+    }
+    let live_before = live();
+    let outcome = match check.panics {
+        None => Ok(exercise()),
+        Some(_) => panic::catch_unwind(exercise),
+    };
+    // An unfinished exercise stops at its todo!, whatever it should panic with
+    let outcome = match outcome {
+        Err(payload) if panic_message(&*payload).contains(protocol::INCOMPLETE) => panic::resume_unwind(payload),
+        outcome => outcome,
+    };
+    // The report allocates too; that SPACE is not the exercise's
+    let live_report = live();
+
+    // (passed, what was checked)
+    let mut results: Vec<(bool, String)> = Vec::new();
+    match (&outcome, check.panics) {
+        (Ok(value), None) => {
+            for (expected, invariant) in &check.returns {
+                let what = format!("returns {expected}");
+                if invariant(value) {
+                    results.push((true, what));
+                } else {
+                    results.push((false, format!("{what}, but returned {value:?}")));
+                }
+            }
+        }
+        (Ok(value), Some(expected)) => {
+            results.push((false, format!("panics with {expected:?}, but it returned {value:?}")));
+        }
+        (Err(payload), Some(expected)) => {
+            let message = panic_message(&**payload);
+            if message.contains(expected) {
+                results.push((true, format!("panics with {expected:?}")));
+            } else {
+                results.push((false, format!("panics with {expected:?}, but it panicked with {message:?}")));
+            }
+        }
+        (Err(_), None) => unreachable!("panics are only caught when one is expected"),
+    }
+    let report = live() - live_report;
+    drop(outcome);
+    if check.frees {
+        let leaked = live() - live_before - report;
+        let what = "frees all the SPACE it allocates".to_string();
+        if ALLOCATIONS.load(Ordering::Relaxed) == 0 {
+            results.push((false, format!("{what}, but nothing was counted: make check::CountingAllocator the #[global_allocator]")));
+        } else if leaked > 0 {
+            results.push((false, format!("{what}, but {leaked} allocations are still live")));
+        } else {
+            results.push((true, what));
+        }
+    }
+
+    let _ = io::stdout().flush();
+    if learn {
+        println!("{}", Line::End);
+        for patterns in &check.prints {
+            println!("{}", Line::Prints(patterns.iter().map(|pattern| pattern.to_string()).collect()));
+        }
+        for (passed, what) in &results {
+            let line = if *passed { Line::Passed(what.clone()) } else { Line::Failed(what.clone()) };
+            println!("{line}");
+        }
+    }
+    let failed: Vec<&String> = results.iter().filter(|(passed, _)| !passed).map(|(_, what)| what).collect();
+    if !failed.is_empty() {
+        if !learn {
+            for what in failed {
+                eprintln!("check failed: exercise() {what}");
+            }
+        }
+        process::exit(1);
+    }
+    // Only learn sees the output: run alone, say what went unchecked
+    if !learn {
+        for patterns in &check.prints {
+            let patterns: Vec<String> = patterns.iter().map(|pattern| pattern.to_string()).collect();
+            eprintln!("not verified: exercise() {} (run it through learn to check)", protocol::describe_prints(&patterns));
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::new()
+    }
+}
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static FREES: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocations not freed yet.
+fn live() -> isize {
+    ALLOCATIONS.load(Ordering::Relaxed) as isize - FREES.load(Ordering::Relaxed) as isize
+}
+
+/// The system allocator, counting allocations and frees so
+/// [`Check::frees_everything`] can tell whether SPACE leaked.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: check::CountingAllocator = check::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        FREES.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Growing moves the SPACE but it stays one allocation
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
@@ -0,0 +1,74 @@
+//! How an exercise reports its checks to the `learn` runner: lines on stdout
+//! starting with [`PREFIX`], written only when [`ENV`] is set.
+//!
+//! ```text
+//! learn-check: begin
+//! ...whatever exercise() prints...
+//! learn-check: end
+//! learn-check: prints x = 5
+//! learn-check: passed returns 10
+//! ```
+
+use std::fmt;
+
+/// Set by `learn` when it runs an exercise.
+pub const ENV: &str = "LEARN_CHECK";
+
+pub const PREFIX: &str = "learn-check: ";
+
+/// The panic message of the `todo!("Exercise incomplete")` every unfinished
+/// `exercise()` ends with.
+pub const INCOMPLETE: &str = "not yet implemented: Exercise incomplete";
+
+pub enum Line {
+    /// `exercise()` starts; what it prints follows.
+    Begin,
+    /// `exercise()` returned.
+    End,
+    /// Between `Begin` and `End` there are lines containing each pattern, in
+    /// this order. Checked by the runner, which sees the output.
+    Prints(Vec<String>),
+    /// A check the exercise verified itself, and what it checked.
+    Passed(String),
+    Failed(String),
+}
+
+impl Line {
+    pub fn parse(line: &str) -> Option<Line> {
+        let rest = line.strip_prefix(PREFIX)?;
+        let (word, text) = rest.split_once(' ').unwrap_or((rest, ""));
+        Some(match word {
+            "begin" => Line::Begin,
+            "end" => Line::End,
+            "prints" => Line::Prints(text.split('\t').map(String::from).collect()),
+            "passed" => Line::Passed(text.to_string()),
+            "failed" => Line::Failed(text.to_string()),
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Begin => write!(f, "{PREFIX}begin"),
+            Line::End => write!(f, "{PREFIX}end"),
+            // Patterns are matched within one line, so a tab never occurs in one
+            Line::Prints(patterns) => write!(f, "{PREFIX}prints {}", patterns.join("\t")),
+            Line::Passed(what) => write!(f, "{PREFIX}passed {what}"),
+            Line::Failed(what) => write!(f, "{PREFIX}failed {what}"),
+        }
+    }
+}
+
+/// Whether `output` has lines containing each pattern, in order.
+pub fn prints_in_order(output: &str, patterns: &[String]) -> bool {
+    let mut lines = output.lines();
+    patterns.iter().all(|pattern| lines.any(|line| line.contains(pattern.as_str())))
+}
+
+/// `prints "a", then "b"`: how a `Prints` check reads in a report.
+pub fn describe_prints(patterns: &[String]) -> String {
+    let quoted: Vec<String> = patterns.iter().map(|pattern| format!("{pattern:?}")).collect();
+    format!("prints {}", quoted.join(", then "))
+}
@@ -0,0 +1,231 @@
+//! What the compiler says about an exercise, and what its `compile_fail()`
+//! says it should.
+//!
+//! An exercise whose `compile_fail()` holds code that must be rejected marks
+//! each error it expects where the code is, compiletest style:
+//!
+//! ```text
+//! // let b = &mut v[4];  //~ E0499     an E0499 reported at this line
+//! // spawn(...);         //~? E0277    an E0277 reported anywhere
+//! ```
+//!
+//! The code is commented out until the learner uncomments it; the markers
+//! move with it. `compile_fail()` is only built with the section crate's
+//! `compile-fail` feature, so the exercise itself still runs.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::exercises::Verdict;
+
+/// The feature that builds `compile_fail()`.
+pub const FEATURE: &str = "compile-fail";
+
+/// One message from `cargo check --message-format=json`.
+pub struct Diagnostic {
+    /// `error`, `warning`, ...
+    pub level: String,
+    /// `E0499`; `None` for messages without an error code.
+    pub code: Option<String>,
+    pub message: String,
+    pub spans: Vec<Span>,
+    /// As rustc prints it, colors included.
+    pub rendered: String,
+}
+
+/// Where a diagnostic points.
+pub struct Span {
+    /// Relative to the crate, `src/bin/09_conservative.rs`.
+    pub file: String,
+    /// 1-based, where the span starts.
+    pub line: usize,
+    /// Whether this is where the error is, not a note on how it came about.
+    pub primary: bool,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level == "error"
+    }
+
+    pub fn primary(&self) -> Option<&Span> {
+        self.spans.iter().find(|span| span.primary)
+    }
+
+    /// Whether the diagnostic is about the source file at `path`.
+    pub fn in_file(&self, path: &Path) -> bool {
+        self.primary().is_some_and(|span| path.ends_with(&span.file))
+    }
+}
+
+/// `cargo check` one bin of the crate in `dir`, with `features` turned on.
+pub fn check(dir: &Path, bin: &str, features: &[&str]) -> Result<Vec<Diagnostic>, String> {
+    let mut command = Command::new("cargo");
+    command.args(["check", "--quiet", "--message-format=json-diagnostic-rendered-ansi", "--bin", bin]);
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    let output = command.current_dir(dir).output().map_err(|e| format!("cannot run cargo: {e}"))?;
+    let diagnostics: Vec<Diagnostic> = String::from_utf8_lossy(&output.stdout).lines().filter_map(parse).collect();
+    // cargo itself failed, before rustc had anything to say
+    if !output.status.success() && !diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(format!("cargo check failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(diagnostics)
+}
+
+/// A `compiler-message` line of cargo's JSON output.
+fn parse(line: &str) -> Option<Diagnostic> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value["reason"] != "compiler-message" {
+        return None;
+    }
+    let message = &value["message"];
+    let text = |value: &Value| value.as_str().map(String::from);
+    let spans = message["spans"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|span| {
+            Some(Span {
+                file: text(&span["file_name"])?,
+                line: span["line_start"].as_u64()? as usize,
+                primary: span["is_primary"].as_bool().unwrap_or(false),
+            })
+        })
+        .collect();
+    Some(Diagnostic {
+        level: text(&message["level"])?,
+        code: text(&message["code"]["code"]),
+        message: text(&message["message"])?,
+        spans,
+        rendered: text(&message["rendered"]).unwrap_or_default(),
+    })
+}
+
+/// An error `compile_fail()` must be rejected with.
+pub struct Expected {
+    pub code: String,
+    /// The line of its marker; `None` for `//~?`, anywhere in the file.
+    pub line: Option<usize>,
+}
+
+/// The `//~` markers in an exercise's source.
+pub fn expected(source: &str) -> Vec<Expected> {
+    let mut expected = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let Some((_, marker)) = line.split_once("//~") else { continue };
+        let (anywhere, codes) = match marker.strip_prefix('?') {
+            Some(codes) => (true, codes),
+            None => (false, marker),
+        };
+        for code in codes.split_whitespace() {
+            expected.push(Expected { code: code.to_string(), line: if anywhere { None } else { Some(i + 1) } });
+        }
+    }
+    expected
+}
+
+/// One verdict per expected error: whether the errors in `path` include it.
+pub fn judge(expected: &[Expected], diagnostics: &[Diagnostic], path: &Path) -> Vec<Verdict> {
+    let errors: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.is_error() && d.in_file(path)).collect();
+    expected
+        .iter()
+        .map(|expected| {
+            let what = match expected.line {
+                Some(line) => format!("rejected with {} at line {line}", expected.code),
+                None => format!("rejected with {}", expected.code),
+            };
+            let found = errors.iter().any(|error| {
+                error.code.as_deref() == Some(expected.code.as_str())
+                    && expected.line.is_none_or(|line| error.primary().is_some_and(|span| span.line == line))
+            });
+            if found {
+                Verdict { passed: true, what }
+            } else if errors.is_empty() {
+                Verdict { passed: false, what: format!("{what}, but it compiled") }
+            } else {
+                let got: Vec<String> = errors.iter().map(|error| describe(error)).collect();
+                Verdict { passed: false, what: format!("{what}, but got {}", got.join(", ")) }
+            }
+        })
+        .collect()
+}
+
+/// `E0502 at line 41`
+fn describe(error: &Diagnostic) -> String {
+    let line = error.primary().map_or(0, |span| span.line);
+    match &error.code {
+        Some(code) => format!("{code} at line {line}"),
+        None => format!("{:?} at line {line}", error.message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+fn compile_fail() {
+    let r0 = &mut v[0];
+    let r1 = &mut v[4];  //~ E0499
+    spawn(r0);  //~? E0277
+}
+";
+
+    /// A `compiler-message` line as cargo prints it, trimmed to what `parse` reads.
+    fn error(code: &str, file: &str, line: usize) -> Diagnostic {
+        let json = format!(
+            r#"{{"reason":"compiler-message","message":{{"level":"error","code":{{"code":"{code}"}},"message":"{code}","spans":[{{"file_name":"{file}","line_start":{line},"is_primary":true}}],"rendered":""}}}}"#
+        );
+        parse(&json).expect("a compiler-message line")
+    }
+
+    fn verdicts(diagnostics: &[Diagnostic]) -> Vec<(bool, String)> {
+        let path = Path::new("exercises/04-lifetimes/src/bin/07_value_tracking.rs");
+        judge(&expected(SOURCE), diagnostics, path).into_iter().map(|verdict| (verdict.passed, verdict.what)).collect()
+    }
+
+    #[test]
+    fn markers_give_codes_and_lines() {
+        let expected: Vec<(String, Option<usize>)> =
+            expected(SOURCE).into_iter().map(|expected| (expected.code, expected.line)).collect();
+        assert_eq!(expected, [("E0499".to_string(), Some(3)), ("E0277".to_string(), None)]);
+    }
+
+    #[test]
+    fn several_codes_on_one_marker() {
+        let codes: Vec<String> = expected("x;  //~ E0382 E0505").into_iter().map(|expected| expected.code).collect();
+        assert_eq!(codes, ["E0382", "E0505"]);
+    }
+
+    #[test]
+    fn errors_where_marked_pass() {
+        let file = "src/bin/07_value_tracking.rs";
+        let verdicts = verdicts(&[error("E0499", file, 3), error("E0277", file, 40)]);
+        assert_eq!(
+            verdicts,
+            [(true, "rejected with E0499 at line 3".to_string()), (true, "rejected with E0277".to_string())]
+        );
+    }
+
+    #[test]
+    fn errors_on_another_line_or_file_fail() {
+        let verdicts = verdicts(&[error("E0499", "src/bin/07_value_tracking.rs", 2), error("E0277", "src/bin/other.rs", 4)]);
+        assert_eq!(
+            verdicts,
+            [
+                (false, "rejected with E0499 at line 3, but got E0499 at line 2".to_string()),
+                (false, "rejected with E0277, but got E0499 at line 2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_errors_means_it_compiled() {
+        let verdicts = verdicts(&[]);
+        assert!(verdicts.iter().all(|(passed, what)| !passed && what.ends_with(", but it compiled")));
+    }
+}
@@ -0,0 +1,334 @@
+//! Finding the exercises and running them.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use check::protocol::{self, Line};
+
+use crate::diagnostics;
+
+/// Exercises that spin or wait on other threads get this long before they
+/// count as failing.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// One crate under `exercises/`, such as `02-ownership-space`.
+pub struct Section {
+    /// The directory name, `02-ownership-space`.
+    pub dir: String,
+    pub number: u32,
+    pub exercises: Vec<Exercise>,
+}
+
+impl Section {
+    /// `ownership space`
+    pub fn title(&self) -> String {
+        self.dir.split_once('-').map_or(self.dir.as_str(), |(_, rest)| rest).replace('-', " ")
+    }
+}
+
+pub struct Exercise {
+    /// `2.10`: section number, then the bin's number.
+    pub id: String,
+    /// The section directory, relative to `exercises/`.
+    pub section: String,
+    /// The bin name, `10_space_layout`.
+    pub bin: String,
+    /// The source file, relative to the repository root.
+    pub path: PathBuf,
+}
+
+impl Exercise {
+    /// How progress is recorded: stable across renumbering of sections.
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.section, self.bin)
+    }
+}
+
+/// The result of running an exercise.
+pub enum Outcome {
+    Done,
+    /// `exercise()` reached its `todo!("Exercise incomplete")`.
+    Incomplete,
+    /// A compile error, another panic, a non-zero exit or a timeout.
+    Failing(String),
+}
+
+pub struct Run {
+    pub outcome: Outcome,
+    /// What the exercise printed, or the compiler's errors. For an exercise
+    /// with a `compile_fail()`, followed by the errors it was rejected with.
+    pub output: String,
+    /// What its `check()` verified, once `exercise()` returned, and which
+    /// errors its `compile_fail()` was rejected with. `None` for exercises
+    /// without either, or that never got that far.
+    pub checks: Option<Vec<Verdict>>,
+}
+
+/// One check and whether it held.
+pub struct Verdict {
+    pub passed: bool,
+    /// `returns 10`, or `returns 10, but returned 9`.
+    pub what: String,
+}
+
+/// Every section crate under `root/exercises`, in order, with its bins.
+pub fn discover(root: &Path) -> Result<Vec<Section>, String> {
+    let dir = root.join("exercises");
+    let entries = fs::read_dir(&dir).map_err(|e| format!("cannot read {}: {e}", dir.display()))?;
+    let mut sections = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(number) = name.split('-').next().and_then(|n| n.parse::<u32>().ok()) else { continue };
+        let manifest = entry.path().join("Cargo.toml");
+        let Ok(text) = fs::read_to_string(&manifest) else { continue };
+        let mut bins = manifest_bins(&text);
+        if bins.is_empty() {
+            bins = auto_bins(&entry.path())?;
+        }
+        let exercises = bins
+            .into_iter()
+            .map(|(bin, path)| Exercise {
+                id: format!("{number}.{}", bin.split('_').next().unwrap_or(&bin)),
+                section: name.clone(),
+                bin,
+                path: Path::new("exercises").join(&name).join(path),
+            })
+            .collect();
+        sections.push(Section { dir: name, number, exercises });
+    }
+    sections.sort_by_key(|section| section.number);
+    Ok(sections)
+}
+
+/// `(name, path)` of each `[[bin]]` table, in manifest order.
+fn manifest_bins(manifest: &str) -> Vec<(String, String)> {
+    let mut bins = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            bins.extend(current.take());
+            if line == "[[bin]]" {
+                current = Some(Default::default());
+            }
+            continue;
+        }
+        let Some((bin, path)) = &mut current else { continue };
+        let Some((key, value)) = line.split_once('=') else { continue };
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "name" => *bin = value,
+            "path" => *path = value,
+            _ => {}
+        }
+    }
+    bins.extend(current);
+    bins.into_iter()
+        .filter(|(bin, _)| !bin.is_empty())
+        .map(|(bin, path)| {
+            let path = if path.is_empty() { format!("src/bin/{bin}.rs") } else { path };
+            (bin, path)
+        })
+        .collect()
+}
+
+/// Bins cargo finds by itself in `src/bin`, for crates that declare none.
+fn auto_bins(section: &Path) -> Result<Vec<(String, String)>, String> {
+    let dir = section.join("src/bin");
+    let entries = fs::read_dir(&dir).map_err(|e| format!("cannot read {}: {e}", dir.display()))?;
+    let mut bins: Vec<(String, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.file_name().to_string_lossy().into_owned();
+            let bin = file.strip_suffix(".rs")?.to_string();
+            Some((bin, format!("src/bin/{file}")))
+        })
+        .collect();
+    bins.sort();
+    Ok(bins)
+}
+
+/// Build the exercise, run it, and classify how it ended.
+pub fn run(root: &Path, exercise: &Exercise) -> Result<Run, String> {
+    let dir = root.join("exercises").join(&exercise.section);
+    let build = Command::new("cargo")
+        .args(["build", "--quiet", "--color=always", "--bin", &exercise.bin])
+        .current_dir(&dir)
+        .output()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    if !build.status.success() {
+        return Ok(Run {
+            outcome: Outcome::Failing("does not compile".to_string()),
+            output: String::from_utf8_lossy(&build.stderr).into_owned(),
+            checks: None,
+        });
+    }
+
+    let mut child = Command::new("cargo")
+        .args(["run", "--quiet", "--bin", &exercise.bin])
+        .current_dir(&dir)
+        // The todo! panic is expected; its backtrace is noise
+        .env("RUST_BACKTRACE", "0")
+        .env(protocol::ENV, "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| format!("cannot wait for {}: {e}", exercise.bin))? {
+            break Some(status);
+        }
+        if started.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    let (stdout, mut checks) = read_checks(&stdout);
+    let mut output = format!("{stdout}{stderr}");
+
+    let source = fs::read_to_string(root.join(&exercise.path)).unwrap_or_default();
+    let expected = diagnostics::expected(&source);
+    if !expected.is_empty() {
+        let diagnostics = diagnostics::check(&dir, &exercise.bin, &[diagnostics::FEATURE])?;
+        for error in diagnostics.iter().filter(|d| d.is_error() && d.in_file(&exercise.path)) {
+            output.push('\n');
+            output.push_str(error.rendered.trim_end());
+            output.push('\n');
+        }
+        checks.get_or_insert_with(Vec::new).extend(diagnostics::judge(&expected, &diagnostics, &exercise.path));
+    }
+
+    let failed = checks.iter().flatten().filter(|verdict| !verdict.passed).count();
+    let outcome = match status {
+        None => Outcome::Failing(format!("still running after {}s", TIMEOUT.as_secs())),
+        // Rejected or not, compile_fail() does not finish the exercise
+        Some(status) if !status.success() && stderr.contains(protocol::INCOMPLETE) => Outcome::Incomplete,
+        Some(_) if failed > 0 => {
+            Outcome::Failing(format!("{failed} of {} checks failed", checks.as_ref().map_or(0, Vec::len)))
+        }
+        Some(status) if status.success() => Outcome::Done,
+        Some(_) => Outcome::Failing(match panic_message(&stderr) {
+            Some(message) => format!("panicked: {message}"),
+            None => "exited with an error".to_string(),
+        }),
+    };
+    Ok(Run { outcome, output, checks })
+}
+
+/// Take the check protocol's lines out of what the exercise printed, and
+/// judge its checks. What `exercise()` must print is only looked for in what
+/// it printed itself: `example()` often prints the same lines.
+fn read_checks(stdout: &str) -> (String, Option<Vec<Verdict>>) {
+    let mut shown = String::new();
+    let mut printed = String::new();
+    let mut inside = false;
+    let mut checks = None;
+    for line in stdout.lines() {
+        let Some(parsed) = Line::parse(line) else {
+            shown.push_str(line);
+            shown.push('\n');
+            if inside {
+                printed.push_str(line);
+                printed.push('\n');
+            }
+            continue;
+        };
+        let verdict = match parsed {
+            Line::Begin => {
+                inside = true;
+                continue;
+            }
+            Line::End => {
+                inside = false;
+                checks = Some(Vec::new());
+                continue;
+            }
+            Line::Prints(patterns) => {
+                let what = protocol::describe_prints(&patterns);
+                if protocol::prints_in_order(&printed, &patterns) {
+                    Verdict { passed: true, what }
+                } else {
+                    Verdict { passed: false, what: format!("{what}, but it did not") }
+                }
+            }
+            Line::Passed(what) => Verdict { passed: true, what },
+            Line::Failed(what) => Verdict { passed: false, what },
+        };
+        checks.get_or_insert_with(Vec::new).push(verdict);
+    }
+    (shown, checks)
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let mut bytes = Vec::new();
+            let _ = pipe.read_to_end(&mut bytes);
+            text = String::from_utf8_lossy(&bytes).into_owned();
+        }
+        text
+    })
+}
+
+/// The line after `thread 'main' panicked at src/bin/..:`.
+fn panic_message(stderr: &str) -> Option<&str> {
+    let mut lines = stderr.lines();
+    lines.find(|line| line.contains("panicked at"))?;
+    lines.next().map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bin(name: &str, path: &str) -> (String, String) {
+        (name.to_string(), path.to_string())
+    }
+
+    #[test]
+    fn bins_in_manifest_order() {
+        let manifest = r#"
+[package]
+name = "lifetimes"
+
+[[bin]]
+name = "02_two_inputs"
+path = "src/bin/02_two_inputs.rs"
+
+[[bin]]
+path = "src/bin/01_lifetime_validity.rs"
+name = "01_lifetime_validity"
+"#;
+        assert_eq!(
+            manifest_bins(manifest),
+            [bin("02_two_inputs", "src/bin/02_two_inputs.rs"), bin("01_lifetime_validity", "src/bin/01_lifetime_validity.rs")]
+        );
+    }
+
+    #[test]
+    fn bin_without_path_is_in_src_bin() {
+        assert_eq!(manifest_bins("[[bin]]\nname = \"01_cell\"\n"), [bin("01_cell", "src/bin/01_cell.rs")]);
+    }
+
+    #[test]
+    fn other_tables_end_a_bin() {
+        let manifest = "[[bin]]\nname = \"01_cell\"\n\n[dependencies]\nname = \"check\"\npath = \"../check\"\n";
+        assert_eq!(manifest_bins(manifest), [bin("01_cell", "src/bin/01_cell.rs")]);
+    }
+
+    #[test]
+    fn bins_without_name_are_skipped() {
+        assert!(manifest_bins("[[bin]]\npath = \"src/main.rs\"\n").is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+//! Run the exercises and keep track of which are done.
+//!
+//! ```text
+//! learn list          every exercise and how it last ended
+//! learn next          run the first exercise that is not done yet
+//! learn run ID        run one exercise, by id (2.10) or bin name (10_space_layout)
+//! learn status        progress per section
+//! learn watch         run the current exercise on every save, moving on when done
+//! ```
+//!
+//! Exercises are the bins of the section crates under `exercises/`. Running one
+//! builds and runs it with cargo, then records it in `.learn-state`:
+//!
+//! - done: it ran to the end and passed the checks in its `check()`, and the
+//!   code in its `compile_fail()`, if any, is rejected with the errors marked
+//!   `//~` (see `diagnostics`)
+//! - incomplete: `exercise()` still reaches its `todo!("Exercise incomplete")`
+//! - failing: it does not compile, panicked some other way, hung, or failed
+//!   a check
+
+mod diagnostics;
+mod exercises;
+mod state;
+mod watch;
+
+use std::path::{Path, PathBuf};
+use std::{env, process};
+
+use exercises::{Exercise, Outcome, Section};
+use state::{State, Status};
+
+const USAGE: &str = "usage: learn list | next | run ID | status | watch";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let root = root();
+    let sections = exercises::discover(&root).unwrap_or_else(|e| fail(&e));
+    let mut state = State::load(&root).unwrap_or_else(|e| fail(&e));
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["list"] => list(&sections, &state),
+        ["status"] => status(&sections, &state),
+        ["next"] => {
+            match next(&sections, &state) {
+                Some(exercise) => {
+                    run(&root, exercise, &mut state);
+                }
+                None => println!("learn: every exercise is done"),
+            }
+        }
+        ["run", id] => {
+            let exercise = find(&sections, id).unwrap_or_else(|e| fail(&e));
+            run(&root, exercise, &mut state);
+        }
+        ["watch"] => watch::watch(&root, &sections, &mut state).unwrap_or_else(|e| fail(&e)),
+        _ => fail(USAGE),
+    }
+}
+
+/// The repository this tool was built in.
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn fail(message: &str) -> ! {
+    if message.starts_with("usage:") {
+        eprintln!("{message}");
+    } else {
+        eprintln!("learn: {message}");
+    }
+    process::exit(1);
+}
+
+fn find<'a>(sections: &'a [Section], id: &str) -> Result<&'a Exercise, String> {
+    let all = sections.iter().flat_map(|section| &section.exercises);
+    let matches: Vec<&Exercise> = all.filter(|e| e.id == id || e.bin == id).collect();
+    match matches.as_slice() {
+        [exercise] => Ok(exercise),
+        [] => Err(format!("no exercise `{id}`; `learn list` shows them all")),
+        _ => {
+            let ids: Vec<&str> = matches.iter().map(|e| e.id.as_str()).collect();
+            Err(format!("`{id}` is ambiguous, use one of: {}", ids.join(", ")))
+        }
+    }
+}
+
+fn list(sections: &[Section], state: &State) {
+    for section in sections {
+        println!("{} · {}", section.number, section.title());
+        for exercise in &section.exercises {
+            let status = state.get(exercise);
+            println!("  {} {:<6} {:<32} {}", status.mark(), exercise.id, exercise.bin, status.word());
+        }
+    }
+}
+
+fn status(sections: &[Section], state: &State) {
+    let (mut done, mut total) = (0, 0);
+    for section in sections {
+        let count = |wanted| section.exercises.iter().filter(|e| state.get(e) == wanted).count();
+        let section_done = count(Status::Done);
+        let mut notes = Vec::new();
+        for status in [Status::Incomplete, Status::Failing] {
+            if count(status) > 0 {
+                notes.push(format!("{} {}", count(status), status.word()));
+            }
+        }
+        let notes = if notes.is_empty() { String::new() } else { format!("  ({})", notes.join(", ")) };
+        let title = format!("{} · {}", section.number, section.title());
+        let line = format!("{title:<28} {section_done:>3}/{:<3}{notes}", section.exercises.len());
+        println!("{}", line.trim_end());
+        done += section_done;
+        total += section.exercises.len();
+    }
+    println!("{:<28} {done:>3}/{total}", "total");
+    if let Some(exercise) = next(sections, state) {
+        println!("\nnext: {} {} ({})", exercise.id, exercise.bin, exercise.path.display());
+    }
+}
+
+/// The first exercise, in course order, that is not done.
+fn next<'a>(sections: &'a [Section], state: &State) -> Option<&'a Exercise> {
+    sections.iter().flat_map(|section| &section.exercises).find(|e| state.get(e) != Status::Done)
+}
+
+/// Run an exercise, show what happened, and record it.
+fn run(root: &Path, exercise: &Exercise, state: &mut State) -> Status {
+    println!("learn: running {} {} ({})", exercise.id, exercise.bin, exercise.path.display());
+    let result = exercises::run(root, exercise).unwrap_or_else(|e| fail(&e));
+    print!("{}", result.output);
+    if !result.output.is_empty() && !result.output.ends_with('\n') {
+        println!();
+    }
+    let checks = result.checks.as_deref().unwrap_or_default();
+    if !checks.is_empty() {
+        println!();
+    }
+    for verdict in checks {
+        let status = if verdict.passed { Status::Done } else { Status::Failing };
+        println!("{} {}", status.mark(), verdict.what);
+    }
+    match &result.outcome {
+        Outcome::Done if result.checks.as_ref().is_some_and(Vec::is_empty) => {
+            println!("\nlearn: {} is done; it has nothing to verify, so answer its questions for yourself", exercise.id)
+        }
+        Outcome::Done => println!("\nlearn: {} is done", exercise.id),
+        Outcome::Incomplete => {
+            println!("\nlearn: {} is incomplete: replace the todo! in exercise() in {}", exercise.id, exercise.path.display())
+        }
+        Outcome::Failing(reason) => println!("\nlearn: {} is failing: {reason}", exercise.id),
+    }
+    let status = Status::from(&result.outcome);
+    state.set(exercise, status);
+    state.save().unwrap_or_else(|e| fail(&e));
+    status
+}
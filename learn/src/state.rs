@@ -0,0 +1,94 @@
+//! Progress, kept between runs in `.learn-state` at the repository root.
+//!
+//! One line per exercise that has been run: its key and how it last ended.
+//!
+//! ```text
+//! 00-declarations/01_literal done
+//! 00-declarations/02_rebindable_literal incomplete
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::exercises::{Exercise, Outcome};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    NotRun,
+    Incomplete,
+    Failing,
+    Done,
+}
+
+impl Status {
+    pub fn word(self) -> &'static str {
+        match self {
+            Status::NotRun => "not run",
+            Status::Incomplete => "incomplete",
+            Status::Failing => "failing",
+            Status::Done => "done",
+        }
+    }
+
+    pub fn mark(self) -> &'static str {
+        match self {
+            Status::NotRun => "[ ]",
+            Status::Incomplete => "[~]",
+            Status::Failing => "[!]",
+            Status::Done => "[x]",
+        }
+    }
+
+    fn parse(word: &str) -> Option<Status> {
+        [Status::Incomplete, Status::Failing, Status::Done].into_iter().find(|status| status.word() == word)
+    }
+}
+
+impl From<&Outcome> for Status {
+    fn from(outcome: &Outcome) -> Status {
+        match outcome {
+            Outcome::Done => Status::Done,
+            Outcome::Incomplete => Status::Incomplete,
+            Outcome::Failing(_) => Status::Failing,
+        }
+    }
+}
+
+pub struct State {
+    path: PathBuf,
+    statuses: BTreeMap<String, Status>,
+}
+
+impl State {
+    /// Read the state file under `root`; a missing file means nothing has run.
+    pub fn load(root: &Path) -> Result<State, String> {
+        let path = root.join(".learn-state");
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("cannot read {}: {e}", path.display())),
+        };
+        let statuses = text
+            .lines()
+            .filter_map(|line| {
+                let (key, word) = line.trim().rsplit_once(' ')?;
+                Some((key.to_string(), Status::parse(word)?))
+            })
+            .collect();
+        Ok(State { path, statuses })
+    }
+
+    pub fn get(&self, exercise: &Exercise) -> Status {
+        self.statuses.get(&exercise.key()).copied().unwrap_or(Status::NotRun)
+    }
+
+    pub fn set(&mut self, exercise: &Exercise, status: Status) {
+        self.statuses.insert(exercise.key(), status);
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let text: String = self.statuses.iter().map(|(key, status)| format!("{key} {}\n", status.word())).collect();
+        fs::write(&self.path, text).map_err(|e| format!("cannot write {}: {e}", self.path.display()))
+    }
+}
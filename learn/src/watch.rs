@@ -0,0 +1,62 @@
+//! `learn watch`: rerun the current exercise every time its file is saved.
+//!
+//! Changes are picked up with inotify, so nothing polls and nothing listens
+//! on the network. Once the exercise is done the next one starts right away,
+//! in course order.
+
+use std::path::Path;
+
+use crate::exercises::Section;
+use crate::state::{State, Status};
+
+#[cfg(target_os = "linux")]
+pub fn watch(root: &Path, sections: &[Section], state: &mut State) -> Result<(), String> {
+    use std::thread;
+    use std::time::Duration;
+
+    use inotify::{Inotify, WatchMask};
+
+    let mut inotify = Inotify::init().map_err(|e| format!("cannot start inotify: {e}"))?;
+    let mut buffer = [0; 4096];
+    let mut saved = false;
+    while let Some(exercise) = crate::next(sections, state) {
+        if saved {
+            // Clear the screen so only the latest run is on it
+            print!("\x1b[2J\x1b[H");
+        }
+        let status = crate::run(root, exercise, state);
+        if status == Status::Done {
+            saved = false;
+            println!();
+            continue;
+        }
+
+        let path = root.join(&exercise.path);
+        let (Some(dir), Some(file)) = (path.parent(), path.file_name()) else {
+            return Err(format!("{} is not a file", path.display()));
+        };
+        // Editors either write in place or write elsewhere and rename over it
+        let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE;
+        let wd = inotify.watches().add(dir, mask).map_err(|e| format!("cannot watch {}: {e}", dir.display()))?;
+        println!("learn: watching {}; save it to run again, Ctrl-C to stop", exercise.path.display());
+
+        loop {
+            let events = inotify.read_events_blocking(&mut buffer).map_err(|e| format!("cannot read inotify events: {e}"))?;
+            if events.into_iter().any(|event| event.wd == wd && event.name == Some(file)) {
+                break;
+            }
+        }
+        // One save can arrive as several events; let them settle and drop them
+        thread::sleep(Duration::from_millis(100));
+        while inotify.read_events(&mut buffer).is_ok_and(|events| events.into_iter().next().is_some()) {}
+        let _ = inotify.watches().remove(wd);
+        saved = true;
+    }
+    println!("learn: every exercise is done");
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn watch(_: &Path, _: &[Section], _: &mut State) -> Result<(), String> {
+    Err("watch needs inotify, which only Linux has; run `learn next` after each save instead".to_string())
+}
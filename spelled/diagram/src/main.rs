@@ -0,0 +1,214 @@
+//! Draw the stack and heap around each statement of an `explicit!` block.
+//!
+//! ```text
+//! diagram [--update] FILE...
+//! ```
+//!
+//! Walks every `explicit!` block in FILE, simulating the notation on an
+//! abstract SPACE model: `owner`/`name` bindings, `take`, `mem_copy`,
+//! `take_or_mem_copy`, `clone_copy`, `coord_shared`/`coord_exclusive`,
+//! `coord_heap` and `at`. Plain `let`s before the block seed the model.
+//! Prints a before/after diagram for every `let` and assignment.
+//!
+//! With `--update`, nothing is printed. Instead, each comment block directly
+//! above a statement that contains a `// diagram:` line has everything after
+//! that line regenerated in place:
+//!
+//! ```text
+//! // Combination: let owner(y) = take(x)
+//! // diagram:
+//! // Before:          <- from here down to the statement is rewritten
+//! // ...
+//! let owner(y) = take(x);
+//! ```
+//!
+//! The model only knows what it can see: unknown calls become opaque stack
+//! values, and types are never consulted.
+
+mod space;
+
+use std::{env, fs, process};
+
+use proc_macro2::Span;
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Block, Expr, Macro, Stmt};
+
+use space::Space;
+
+fn main() {
+    let mut update = false;
+    let mut paths = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--update" => update = true,
+            _ => paths.push(arg),
+        }
+    }
+    if paths.is_empty() {
+        eprintln!("usage: diagram [--update] FILE...");
+        process::exit(1);
+    }
+
+    for path in &paths {
+        let source = fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("diagram: cannot read {path}: {e}");
+            process::exit(1);
+        });
+        let steps = steps(&source).unwrap_or_else(|e| {
+            let start = e.span().start();
+            eprintln!("diagram: {path}:{}:{}: {e}", start.line, start.column + 1);
+            process::exit(1);
+        });
+
+        if !update {
+            for step in &steps {
+                println!("{path}:{}: `{}`", step.line, step.stmt);
+                for line in step.lines() {
+                    println!("{}", comment(&line));
+                }
+                println!();
+            }
+            continue;
+        }
+
+        let (updated, count) = rewrite(&source, &steps);
+        if updated != source {
+            if let Err(e) = fs::write(path, updated) {
+                eprintln!("diagram: cannot write {path}: {e}");
+                process::exit(1);
+            }
+        }
+        println!("diagram: {path}: {count} diagram(s) regenerated");
+    }
+}
+
+/// One simulated statement, with the SPACE model on either side of it.
+struct Step {
+    /// 1-based line the statement starts on.
+    line: usize,
+    stmt: String,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+impl Step {
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec!["Before:".to_string()];
+        lines.extend(self.before.iter().cloned());
+        lines.push(String::new());
+        lines.push("After:".to_string());
+        lines.extend(self.after.iter().cloned());
+        lines
+    }
+}
+
+fn steps(source: &str) -> syn::Result<Vec<Step>> {
+    let file = syn::parse_file(source)?;
+    let mut walker = Walker { source: source.lines().collect(), steps: Vec::new(), error: None };
+    walker.visit_file(&file);
+    match walker.error {
+        Some(e) => Err(e),
+        None => Ok(walker.steps),
+    }
+}
+
+struct Walker<'a> {
+    source: Vec<&'a str>,
+    steps: Vec<Step>,
+    error: Option<syn::Error>,
+}
+
+impl Walker<'_> {
+    fn explicit(&mut self, space: &mut Space, mac: &Macro) {
+        let stmts = match Block::parse_within.parse2(mac.tokens.clone()) {
+            Ok(stmts) => stmts,
+            Err(e) => {
+                self.error.get_or_insert(e);
+                return;
+            }
+        };
+        for stmt in &stmts {
+            let before = space.draw();
+            if space.step(stmt) {
+                self.steps.push(Step {
+                    line: stmt.span().start().line,
+                    stmt: self.snippet(stmt.span()),
+                    before,
+                    after: space.draw(),
+                });
+            }
+        }
+    }
+
+    /// The statement's source text, on one line.
+    fn snippet(&self, span: Span) -> String {
+        let (start, end) = (span.start(), span.end());
+        let mut text = String::new();
+        for line in start.line..=end.line {
+            let chars: Vec<_> = self.source[line - 1].chars().collect();
+            let from = if line == start.line { start.column } else { 0 };
+            let to = if line == end.line { end.column } else { chars.len() };
+            text.push_str(&chars[from.min(chars.len())..to.min(chars.len())].iter().collect::<String>());
+            text.push(' ');
+        }
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl<'ast> Visit<'ast> for Walker<'_> {
+    fn visit_block(&mut self, block: &'ast Block) {
+        let mut space = Space::default();
+        for stmt in &block.stmts {
+            let mac = match stmt {
+                Stmt::Macro(stmt) => Some(&stmt.mac),
+                Stmt::Expr(Expr::Macro(expr), _) => Some(&expr.mac),
+                _ => None,
+            };
+            match mac {
+                Some(mac) if mac.path.is_ident("explicit") => self.explicit(&mut space, mac),
+                _ => {
+                    space.step(stmt);
+                }
+            }
+        }
+        visit::visit_block(self, block);
+    }
+}
+
+/// Regenerate the lines after each `// diagram:` marker. Returns the new
+/// source and how many diagrams were written.
+fn rewrite(source: &str, steps: &[Step]) -> (String, usize) {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let mut count = 0;
+    let mut steps: Vec<_> = steps.iter().collect();
+    steps.sort_by_key(|step| std::cmp::Reverse(step.line));
+    for step in steps {
+        let stmt = step.line - 1;
+        let mut top = stmt;
+        while top > 0 && lines[top - 1].trim_start().starts_with("//") {
+            top -= 1;
+        }
+        let Some(marker) = (top..stmt).find(|&i| lines[i].trim() == "// diagram:") else {
+            continue;
+        };
+        let indent: String = lines[marker].chars().take_while(|c| c.is_whitespace()).collect();
+        let diagram = step.lines().into_iter().map(|line| format!("{indent}{}", comment(&line)));
+        lines.splice(marker + 1..stmt, diagram);
+        count += 1;
+    }
+    let mut updated = lines.join("\n");
+    if source.ends_with('\n') {
+        updated.push('\n');
+    }
+    (updated, count)
+}
+
+fn comment(line: &str) -> String {
+    if line.is_empty() {
+        "//".to_string()
+    } else {
+        format!("// {line}")
+    }
+}
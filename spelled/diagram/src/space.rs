@@ -0,0 +1,513 @@
+//! The abstract SPACE model: stack slots, heap cells, and the bindings that
+//! label them. `Space::step` simulates one statement; `Space::draw` renders
+//! the current state in the box style the exercises use.
+
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{Expr, Lit, Member, Pat, Stmt, Token, UnOp};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Loc {
+    Stack(usize),
+    Heap(usize),
+}
+
+/// The contents of one SPACE.
+#[derive(Clone)]
+struct Cell {
+    text: String,
+    /// Where the coordinates held in this SPACE lead.
+    points_to: Option<Loc>,
+    /// Whether this SPACE owns what it points to (Box, String, Vec).
+    owns: bool,
+    /// The fields of a tuple, each a SPACE of its own.
+    parts: Vec<Cell>,
+}
+
+impl Cell {
+    fn value(text: impl Into<String>) -> Cell {
+        Cell { text: text.into(), points_to: None, owns: false, parts: Vec::new() }
+    }
+
+    fn coord(to: Option<Loc>) -> Cell {
+        Cell { points_to: to, ..Cell::value("ptr") }
+    }
+
+    fn tuple(parts: Vec<Cell>) -> Cell {
+        Cell { parts, ..Cell::value("") }
+    }
+
+    fn owns_heap(&self) -> bool {
+        self.owns || self.parts.iter().any(Cell::owns_heap)
+    }
+
+    fn describe(&self) -> String {
+        if self.parts.is_empty() {
+            return self.text.clone();
+        }
+        let parts: Vec<_> = self.parts.iter().map(Cell::describe).collect();
+        format!("({})", parts.join(", "))
+    }
+
+    fn targets(&self) -> Vec<Loc> {
+        let mut targets: Vec<_> = self.points_to.into_iter().collect();
+        for part in &self.parts {
+            targets.extend(part.targets());
+        }
+        targets
+    }
+}
+
+struct Binding {
+    name: String,
+    label: String,
+    slot: usize,
+    valid: bool,
+}
+
+/// What evaluating the right side of a statement leaves behind.
+enum Produced {
+    /// An existing stack slot whose SPACE changes hands (`take`).
+    Slot(usize),
+    /// New contents for a fresh slot.
+    Cell(Cell),
+}
+
+#[derive(Default)]
+pub struct Space {
+    stack: Vec<Option<Cell>>,
+    heap: Vec<Option<Cell>>,
+    bindings: Vec<Binding>,
+}
+
+impl Space {
+    /// Apply one statement. Returns false for statements that cannot change
+    /// the SPACE model (macros, bare expressions).
+    pub fn step(&mut self, stmt: &Stmt) -> bool {
+        match stmt {
+            Stmt::Local(local) => {
+                if let Some(init) = &local.init {
+                    self.bind_pat(&local.pat, &init.expr);
+                }
+                true
+            }
+            Stmt::Expr(Expr::Assign(assign), _) => {
+                self.assign(&assign.left, &assign.right);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn bind_pat(&mut self, pat: &Pat, init: &Expr) {
+        match (pat, init) {
+            (Pat::Type(typed), _) => self.bind_pat(&typed.pat, init),
+            (Pat::Paren(paren), _) => self.bind_pat(&paren.pat, init),
+            (_, Expr::Paren(paren)) => self.bind_pat(pat, &paren.expr),
+            (Pat::Tuple(pats), Expr::Tuple(inits)) if pats.elems.len() == inits.elems.len() => {
+                for (pat, init) in pats.elems.iter().zip(&inits.elems) {
+                    self.bind_pat(pat, init);
+                }
+            }
+            (Pat::Tuple(pats), _) => {
+                let parts = self.unpack(init, pats.elems.len());
+                for (pat, part) in pats.elems.iter().zip(parts) {
+                    self.bind(pat, None, Produced::Cell(part));
+                }
+            }
+            _ => {
+                let produced = self.eval(init);
+                self.bind(pat, Some(init), produced);
+            }
+        }
+    }
+
+    fn bind(&mut self, pat: &Pat, init: Option<&Expr>, produced: Produced) {
+        let Some((name, label)) = binding_of(pat, init) else {
+            return;
+        };
+        let slot = match produced {
+            Produced::Slot(slot) => slot,
+            Produced::Cell(cell) => {
+                self.stack.push(Some(cell));
+                self.stack.len() - 1
+            }
+        };
+        self.bindings.push(Binding { name, label, slot, valid: true });
+    }
+
+    /// Split a tuple-valued expression into its fields. Moving out a field
+    /// that owns heap memory invalidates the tuple's bindings.
+    fn unpack(&mut self, init: &Expr, len: usize) -> Vec<Cell> {
+        let cell = match self.read(init) {
+            Some(cell) => {
+                if let (Some(Loc::Stack(slot)), true) = (self.place(init), cell.owns_heap()) {
+                    self.invalidate(slot);
+                }
+                cell
+            }
+            None => self.eval_cell(init),
+        };
+        let mut parts = cell.parts;
+        parts.resize_with(len, || Cell::value("?"));
+        parts
+    }
+
+    fn assign(&mut self, left: &Expr, right: &Expr) {
+        let Some(loc) = self.place(left) else {
+            return;
+        };
+        let cell = self.eval_cell(right);
+        let old = match loc {
+            Loc::Stack(slot) if self.bindings.iter().any(|b| b.slot == slot && b.valid) => self.stack[slot].take(),
+            Loc::Stack(_) => None,
+            Loc::Heap(cell) => self.heap[cell].take(),
+        };
+        if let Some(old) = old {
+            self.free(old);
+        }
+        match loc {
+            Loc::Stack(slot) => {
+                self.stack[slot] = Some(cell);
+                if let Expr::Path(path) = left {
+                    for binding in self.bindings.iter_mut().filter(|b| b.slot == slot) {
+                        binding.valid = path.path.is_ident(&binding.name);
+                    }
+                }
+            }
+            Loc::Heap(heap) => self.heap[heap] = Some(cell),
+        }
+    }
+
+    fn eval_cell(&mut self, expr: &Expr) -> Cell {
+        match self.eval(expr) {
+            Produced::Slot(slot) => self.stack[slot].clone().unwrap_or_else(|| Cell::value("?")),
+            Produced::Cell(cell) => cell,
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr) -> Produced {
+        match expr {
+            Expr::Paren(paren) => self.eval(&paren.expr),
+            Expr::Group(group) => self.eval(&group.expr),
+            Expr::Lit(lit) => Produced::Cell(Cell::value(literal(&lit.lit))),
+            Expr::Tuple(tuple) => {
+                let parts = tuple.elems.iter().map(|elem| self.eval_cell(elem)).collect();
+                Produced::Cell(Cell::tuple(parts))
+            }
+            Expr::Reference(reference) => Produced::Cell(Cell::coord(self.place(&reference.expr))),
+            Expr::Unary(unary) if matches!(unary.op, UnOp::Deref(_)) => self.mem_copy(expr),
+            Expr::Path(_) | Expr::Field(_) => self.take_or_mem_copy(expr),
+            Expr::Macro(mac) if mac.mac.path.is_ident("vec") => {
+                let elems = mac
+                    .mac
+                    .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                    .map(|elems| elems.iter().map(text).collect::<Vec<_>>().join(", "))
+                    .unwrap_or_default();
+                Produced::Cell(self.alloc("ptr/len/cap", Cell::value(format!("[{elems}]"))))
+            }
+            Expr::MethodCall(call) if call.method == "to_string" || call.method == "to_owned" => {
+                Produced::Cell(self.alloc("ptr/len/cap", Cell::value(text(&call.receiver))))
+            }
+            Expr::MethodCall(call) if call.method == "clone" => self.clone_copy(&call.receiver),
+            Expr::Call(call) => {
+                let Expr::Path(func) = &*call.func else {
+                    return Produced::Cell(Cell::value(text(expr)));
+                };
+                let path: Vec<_> = func.path.segments.iter().map(|seg| seg.ident.to_string()).collect();
+                let path: Vec<_> = path.iter().map(String::as_str).collect();
+                match (path.as_slice(), call.args.first()) {
+                    (["String", "from"], Some(arg)) => Produced::Cell(self.alloc("ptr/len/cap", Cell::value(text(arg)))),
+                    (["take"], Some(arg)) => self.take(arg),
+                    (["mem_copy"], Some(arg)) => self.mem_copy(arg),
+                    (["at"], Some(_)) => self.mem_copy(expr),
+                    (["take_or_mem_copy"], Some(arg)) => self.take_or_mem_copy(arg),
+                    (["clone_copy"], Some(arg)) => self.clone_copy(arg),
+                    (["coord_shared" | "coord_exclusive" | "coord_raw" | "coord_raw_exclusive"], Some(arg)) => {
+                        Produced::Cell(Cell::coord(self.place(arg)))
+                    }
+                    (
+                        ["coord_heap" | "coord_counted" | "coord_counted_atomic"]
+                        | ["Box" | "Rc" | "Arc", "new"],
+                        Some(arg),
+                    ) => {
+                        let inner = self.eval_cell(arg);
+                        Produced::Cell(self.alloc("ptr", inner))
+                    }
+                    _ => Produced::Cell(Cell::value(text(expr))),
+                }
+            }
+            _ => Produced::Cell(Cell::value(text(expr))),
+        }
+    }
+
+    fn take(&mut self, expr: &Expr) -> Produced {
+        match self.place(expr) {
+            Some(Loc::Stack(slot)) => {
+                self.invalidate(slot);
+                Produced::Slot(slot)
+            }
+            Some(Loc::Heap(heap)) => {
+                // moving out of a Box frees the Box; its owner is spent
+                for slot in 0..self.stack.len() {
+                    if let Some(cell) = &mut self.stack[slot] {
+                        if cell.owns && cell.points_to == Some(Loc::Heap(heap)) {
+                            cell.points_to = None;
+                            cell.owns = false;
+                            self.invalidate(slot);
+                        }
+                    }
+                }
+                let cell = self.heap[heap].take().unwrap_or_else(|| Cell::value("?"));
+                Produced::Cell(cell)
+            }
+            None => match self.read(expr) {
+                // a field: partially moves out of the tuple
+                Some(cell) => {
+                    if cell.owns_heap() {
+                        if let Some(Loc::Stack(slot)) = root(expr).and_then(|root| self.place(root)) {
+                            self.invalidate(slot);
+                        }
+                    }
+                    Produced::Cell(cell)
+                }
+                None => self.eval(expr),
+            },
+        }
+    }
+
+    fn mem_copy(&mut self, expr: &Expr) -> Produced {
+        match self.read(expr) {
+            Some(cell) => Produced::Cell(Cell { owns: false, ..cell }),
+            None => self.eval(expr),
+        }
+    }
+
+    fn take_or_mem_copy(&mut self, expr: &Expr) -> Produced {
+        match self.read(expr) {
+            Some(cell) if cell.owns_heap() => self.take(expr),
+            Some(_) => self.mem_copy(expr),
+            None => self.eval(expr),
+        }
+    }
+
+    fn clone_copy(&mut self, expr: &Expr) -> Produced {
+        match self.read(expr) {
+            Some(cell) => Produced::Cell(self.deep_clone(&cell)),
+            None => self.eval(expr),
+        }
+    }
+
+    fn deep_clone(&mut self, cell: &Cell) -> Cell {
+        let mut clone = cell.clone();
+        if let (true, Some(Loc::Heap(heap))) = (cell.owns, cell.points_to) {
+            if let Some(target) = self.heap[heap].clone() {
+                let target = self.deep_clone(&target);
+                self.heap.push(Some(target));
+                clone.points_to = Some(Loc::Heap(self.heap.len() - 1));
+            }
+        }
+        clone.parts = cell.parts.iter().map(|part| self.deep_clone(part)).collect();
+        clone
+    }
+
+    fn alloc(&mut self, text: &str, target: Cell) -> Cell {
+        self.heap.push(Some(target));
+        Cell {
+            points_to: Some(Loc::Heap(self.heap.len() - 1)),
+            owns: true,
+            ..Cell::value(text)
+        }
+    }
+
+    fn free(&mut self, cell: Cell) {
+        if let (true, Some(Loc::Heap(heap))) = (cell.owns, cell.points_to) {
+            if let Some(target) = self.heap[heap].take() {
+                self.free(target);
+            }
+        }
+        for part in cell.parts {
+            self.free(part);
+        }
+    }
+
+    fn invalidate(&mut self, slot: usize) {
+        for binding in self.bindings.iter_mut().filter(|b| b.slot == slot) {
+            binding.valid = false;
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.bindings.iter().rev().find(|b| b.name == name)
+    }
+
+    /// The SPACE a place expression names: a binding's slot, or where
+    /// `at(r)` / `*r` leads.
+    fn place(&self, expr: &Expr) -> Option<Loc> {
+        match expr {
+            Expr::Paren(paren) => self.place(&paren.expr),
+            Expr::Path(path) => {
+                let name = path.path.get_ident()?.to_string();
+                Some(Loc::Stack(self.lookup(&name)?.slot))
+            }
+            _ => self.read(deref_of(expr)?)?.points_to,
+        }
+    }
+
+    /// The contents of a place expression, fields included.
+    fn read(&self, expr: &Expr) -> Option<Cell> {
+        if let Expr::Field(field) = expr {
+            let Member::Unnamed(index) = &field.member else {
+                return None;
+            };
+            return self.read(&field.base)?.parts.get(index.index as usize).cloned();
+        }
+        match self.place(expr)? {
+            Loc::Stack(slot) => self.stack[slot].clone(),
+            Loc::Heap(heap) => self.heap[heap].clone(),
+        }
+    }
+
+    pub fn draw(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let stack: Vec<_> = (0..self.stack.len())
+            .filter_map(|slot| {
+                let cell = self.stack[slot].as_ref()?;
+                let mut notes = self.arrows(cell);
+                for binding in self.bindings.iter().filter(|b| b.slot == slot) {
+                    notes.push(if binding.valid {
+                        format!("<--- {} ({})", binding.name, binding.label)
+                    } else {
+                        format!("<... {} (invalid)", binding.name)
+                    });
+                }
+                Some((cell.describe(), notes.join("  ")))
+            })
+            .collect();
+        lines.push("Stack:".to_string());
+        boxed(&mut lines, &stack);
+
+        let heap: Vec<_> = (0..self.heap.len())
+            .filter_map(|heap| {
+                let cell = self.heap[heap].as_ref()?;
+                let mut notes = self.arrows(cell);
+                notes.push(format!("(heap #{heap})"));
+                Some((cell.describe(), notes.join("  ")))
+            })
+            .collect();
+        if !heap.is_empty() {
+            lines.push("Heap:".to_string());
+            boxed(&mut lines, &heap);
+        }
+        lines
+    }
+
+    fn arrows(&self, cell: &Cell) -> Vec<String> {
+        let targets: Vec<_> = cell
+            .targets()
+            .into_iter()
+            .map(|loc| match loc {
+                Loc::Heap(heap) => format!("heap #{heap}"),
+                Loc::Stack(slot) => {
+                    let owner = self.bindings.iter().find(|b| b.slot == slot && b.valid);
+                    match owner.or_else(|| self.bindings.iter().find(|b| b.slot == slot)) {
+                        Some(binding) => format!("({}'s slot)", binding.name),
+                        None => "(a slot)".to_string(),
+                    }
+                }
+            })
+            .collect();
+        if targets.is_empty() {
+            Vec::new()
+        } else {
+            vec![format!("---> {}", targets.join(", "))]
+        }
+    }
+}
+
+/// The name and label a pattern gives its binding.
+fn binding_of(pat: &Pat, init: Option<&Expr>) -> Option<(String, String)> {
+    let shared_or_exclusive = || match init {
+        Some(Expr::Reference(reference)) if reference.mutability.is_some() => ", exclusive",
+        Some(Expr::Reference(_)) => ", shared",
+        Some(Expr::Call(call)) => match &*call.func {
+            Expr::Path(path) if path.path.is_ident("coord_exclusive") => ", exclusive",
+            Expr::Path(path) if path.path.is_ident("coord_shared") => ", shared",
+            _ => "",
+        },
+        _ => "",
+    };
+    match pat {
+        Pat::Type(typed) => binding_of(&typed.pat, init),
+        Pat::Ident(ident) => {
+            let marker = if matches!(init, Some(Expr::Reference(_))) { "name" } else { "owner" };
+            let rebindable = if ident.mutability.is_some() { ", rebindable" } else { "" };
+            let coords = if marker == "name" { shared_or_exclusive() } else { "" };
+            Some((ident.ident.to_string(), format!("{marker}{coords}{rebindable}")))
+        }
+        Pat::TupleStruct(marker) if marker.elems.len() == 1 => {
+            let marker_name = marker.path.get_ident()?.to_string();
+            let (ident, rebindable) = match &marker.elems[0] {
+                Pat::Ident(ident) => (ident.ident.to_string(), ""),
+                Pat::TupleStruct(inner) if inner.path.is_ident("rebindable") => match inner.elems.first()? {
+                    Pat::Ident(ident) => (ident.ident.to_string(), ", rebindable"),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let coords = if marker_name == "name" { shared_or_exclusive() } else { "" };
+            Some((ident, format!("{marker_name}{coords}{rebindable}")))
+        }
+        _ => None,
+    }
+}
+
+/// `r` in `at(r)` or `*r`.
+fn deref_of(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Deref(_)) => Some(&unary.expr),
+        Expr::Call(call) if call.args.len() == 1 => match &*call.func {
+            Expr::Path(path) if path.path.is_ident("at") => call.args.first(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The binding a field expression like `t.0.1` starts from.
+fn root(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Field(field) => root(&field.base),
+        Expr::Path(_) => Some(expr),
+        _ => None,
+    }
+}
+
+fn literal(lit: &Lit) -> String {
+    lit.to_token_stream().to_string()
+}
+
+fn text(expr: &Expr) -> String {
+    match expr {
+        Expr::Lit(lit) => literal(&lit.lit),
+        _ => expr.to_token_stream().to_string(),
+    }
+}
+
+fn boxed(lines: &mut Vec<String>, rows: &[(String, String)]) {
+    if rows.is_empty() {
+        lines.push("(empty)".to_string());
+        return;
+    }
+    let width = rows.iter().map(|(text, _)| text.chars().count()).max().unwrap_or(0) + 2;
+    let rule = "─".repeat(width);
+    lines.push(format!("┌{rule}┐"));
+    for (i, (text, notes)) in rows.iter().enumerate() {
+        if i > 0 {
+            lines.push(format!("├{rule}┤"));
+        }
+        let row = format!("│ {text:<0$} │ {notes}", width - 2);
+        lines.push(row.trim_end().to_string());
+    }
+    lines.push(format!("└{rule}┘"));
+}
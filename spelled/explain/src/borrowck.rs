@@ -0,0 +1,385 @@
+use crate::{Diagnostic, Span};
+
+// rustc's labels are the stable part of a borrow-check error: each span says
+// what happens there ("first mutable borrow occurs here", "borrow later used
+// here"), so spans are found by label. Bindings are read off the source line
+// a coordinate is created on, `let r = &x;` or `r = &x;`.
+
+/// A borrow-check error, told in course terms.
+pub struct Explanation<'a> {
+    /// What went wrong, in SPACE, TIME and COORDINATES.
+    pub summary: String,
+    /// What happens on each line involved.
+    pub notes: Vec<(&'a Span, String)>,
+    pub section: &'static Section,
+}
+
+/// A section of the course that explains a rule.
+pub struct Section {
+    /// The chapter's file under `course/`.
+    pub chapter: &'static str,
+    /// The section's heading.
+    pub title: &'static str,
+}
+
+impl Section {
+    /// `course/ch03-how-compilers-track-references.md#detecting-dead-space`
+    pub fn link(&self) -> String {
+        let anchor: String = self
+            .title
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-')
+            .map(|c| if c == ' ' { '-' } else { c })
+            .collect();
+        format!("course/{}#{anchor}", self.chapter)
+    }
+}
+
+const DEAD_SPACE: Section = Section { chapter: "ch03-how-compilers-track-references.md", title: "Detecting Dead Space" };
+const USING_DATA: Section = Section { chapter: "ch03-how-compilers-track-references.md", title: "Using Data, Managing Space" };
+const INTERACTIONS: Section = Section { chapter: "ch02-space-time-coordinates.md", title: "Bugs as Interaction Failures" };
+const FEATURES: Section = Section { chapter: "ch02-space-time-coordinates.md", title: "Features as Interaction Solutions" };
+
+/// The explanation of an ownership error; `None` for any other error.
+pub fn explain(diagnostic: &Diagnostic) -> Option<Explanation<'_>> {
+    let error = Error { diagnostic, primary: diagnostic.spans.iter().find(|span| span.primary)? };
+    match diagnostic.code.as_deref()? {
+        "E0382" => error.used_after_move(),
+        "E0499" => error.two_exclusive(),
+        "E0502" => error.shared_and_exclusive(),
+        "E0505" => error.moved_while_named(),
+        "E0506" => error.written_while_named(),
+        "E0597" => error.outlives_owner(),
+        "E0716" => error.temporary_dies(),
+        "E0373" => error.closure_outlives(),
+        _ => None,
+    }
+}
+
+/// How a coordinate reaches its SPACE.
+#[derive(Clone, Copy)]
+enum Coord {
+    Shared,
+    Exclusive,
+}
+
+impl Coord {
+    /// `&x` or `&mut x`; `None` for a coordinate made out of sight, like the
+    /// `&mut self` of a method call.
+    fn written(snippet: &str) -> Option<Coord> {
+        if snippet.starts_with("&mut ") {
+            Some(Coord::Exclusive)
+        } else if snippet.starts_with('&') {
+            Some(Coord::Shared)
+        } else {
+            None
+        }
+    }
+
+    /// From a label: `immutable borrow occurs here`, `mutable borrow ...`.
+    fn labelled(label: &str) -> Coord {
+        if label.contains("immutable") {
+            Coord::Shared
+        } else {
+            Coord::Exclusive
+        }
+    }
+
+    /// `coord_shared(x)`
+    fn notation(self, place: &str) -> String {
+        match self {
+            Coord::Shared => format!("coord_shared({place})"),
+            Coord::Exclusive => format!("coord_exclusive({place})"),
+        }
+    }
+}
+
+struct Error<'a> {
+    diagnostic: &'a Diagnostic,
+    primary: &'a Span,
+}
+
+impl<'a> Error<'a> {
+    /// The span whose label contains `label`, in the primary span's file.
+    fn span(&self, label: &str) -> Option<&'a Span> {
+        self.diagnostic.spans.iter().find(|span| span.file == self.primary.file && span.label.contains(label))
+    }
+
+    /// Where a coordinate is used after the conflict: `borrow later used here`.
+    fn later_use(&self) -> Option<&'a Span> {
+        self.span("later")
+    }
+
+    /// The binding the error is about: the first `` `x` `` in the message.
+    fn place(&self) -> String {
+        let message = &self.diagnostic.message;
+        message.split('`').nth(1).unwrap_or("the value").to_string()
+    }
+
+    fn used_after_move(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let moved = self.span("moved")?;
+        let mut notes = Vec::new();
+        if let Some(declared) = self.span("does not implement the `Copy` trait") {
+            let ty = declared.label.split('`').nth(3).unwrap_or("its type");
+            notes.push((declared, format!("owner({place}): {ty} is not Copy, so it moves")));
+        }
+        let summary = if moved.label.contains("previous iteration of loop") {
+            notes.push((moved, format!("take({place}) on every pass of the loop")));
+            format!(
+                "take({place}) at line {} moves {place}'s SPACE to a new owner on every pass of the loop, \
+                 but after the first pass {place} owns nothing to take.",
+                moved.line,
+            )
+        } else {
+            notes.push((moved, format!("take({place}): its SPACE moves to a new owner")));
+            notes.push((self.primary, format!("✗ {place} used, but it owns no SPACE any more")));
+            format!(
+                "take({place}) at line {} moved {place}'s SPACE to a new owner, so {place} owns nothing from \
+                 then on. Line {} still uses {place}. clone_copy({place}) would make new SPACE instead.",
+                moved.line, self.primary.line,
+            )
+        };
+        Some(Explanation { summary, notes, section: &USING_DATA })
+    }
+
+    fn two_exclusive(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let first = self.span("first mutable borrow")?;
+        let used = self.later_use();
+        let holder = holder(first, used);
+        let coord = Coord::Exclusive.notation(&place);
+        let mut notes = vec![
+            (first, holds(&holder, &coord)),
+            (self.primary, format!("✗ a second {coord} while {holder} is live")),
+        ];
+        let summary = match used {
+            Some(used) => {
+                notes.push((used, format!("{holder} used: its coordinate is live until here")));
+                format!(
+                    "{} at line {} is still used at line {}, and line {} makes a second {coord} before that. \
+                     Only one exclusive coordinate may reach a SPACE at a time.",
+                    subject(&holder, &coord), first.line, used.line, self.primary.line,
+                )
+            }
+            None => format!(
+                "{} at line {} is still live when line {} makes a second one. Only one exclusive \
+                 coordinate may reach a SPACE at a time.",
+                subject(&holder, &coord), first.line, self.primary.line,
+            ),
+        };
+        Some(Explanation { summary: opened_by(&holder, summary), notes, section: &INTERACTIONS })
+    }
+
+    fn shared_and_exclusive(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let wanted = Coord::labelled(&self.primary.label);
+        let held = self.diagnostic.spans.iter().find(|span| {
+            !span.primary && span.file == self.primary.file && span.label.contains("borrow occurs here")
+        })?;
+        let holding = Coord::labelled(&held.label);
+        let used = self.later_use();
+        let holder = holder(held, used);
+        let mut notes = vec![
+            (held, holds(&holder, &holding.notation(&place))),
+            (self.primary, format!("✗ `{}` needs {} while {holder} is live", self.primary.snippet, wanted.notation(&place))),
+        ];
+        let during = match used {
+            Some(used) => {
+                notes.push((used, format!("{holder} used: its coordinate is live until here")));
+                format!("is still used at line {}", used.line)
+            }
+            None => "is still live".to_string(),
+        };
+        let summary = format!(
+            "{} at line {} {during}, and line {} needs {} in between. Shared coordinates and an exclusive one \
+             cannot reach the same SPACE at the same TIME.",
+            subject(&holder, &holding.notation(&place)),
+            held.line,
+            self.primary.line,
+            wanted.notation(&place),
+        );
+        Some(Explanation { summary: opened_by(&holder, summary), notes, section: &INTERACTIONS })
+    }
+
+    fn moved_while_named(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let borrow = self.span("borrow of")?;
+        let used = self.later_use();
+        let holder = holder(borrow, used);
+        let mut notes = vec![
+            (borrow, names(&holder, &place, borrow)),
+            (self.primary, format!("✗ take({place}) while {holder} names its SPACE")),
+        ];
+        let until = used.map_or(String::new(), |used| {
+            notes.push((used, format!("{holder} used: its coordinate is live until here")));
+            format!(" and is still used at line {}", used.line)
+        });
+        let summary = format!(
+            "{holder} names {place}'s SPACE from line {}{until}, but line {} takes {place} in between: \
+             take({place}) moves the SPACE to a new owner while a coordinate still points at the old one.",
+            borrow.line, self.primary.line,
+        );
+        Some(Explanation { summary: opened_by(&holder, summary), notes, section: &USING_DATA })
+    }
+
+    fn written_while_named(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let borrow = self.span("borrowed here")?;
+        let used = self.later_use();
+        let holder = holder(borrow, used);
+        let mut notes = vec![
+            (borrow, names(&holder, &place, borrow)),
+            (self.primary, format!("✗ {place} written while {holder} names its SPACE")),
+        ];
+        let until = used.map_or(String::new(), |used| {
+            notes.push((used, format!("{holder} used: its coordinate is live until here")));
+            format!(" and is still used at line {}", used.line)
+        });
+        let summary = format!(
+            "{holder} names {place}'s SPACE from line {}{until}, but line {} writes to {place} in between. \
+             Not even the owner may change SPACE while a coordinate to it is live.",
+            borrow.line, self.primary.line,
+        );
+        Some(Explanation { summary: opened_by(&holder, summary), notes, section: &INTERACTIONS })
+    }
+
+    fn outlives_owner(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let dropped = self.span("dropped here")?;
+        let used = self.later_use();
+        let holder = holder(self.primary, used);
+        let mut notes = Vec::new();
+        if let Some(declared) = self.span("declared here") {
+            notes.push((declared, format!("owner({place}): its SPACE starts")));
+        }
+        notes.push((self.primary, names(&holder, &place, self.primary)));
+        notes.push((dropped, format!("✗ {place}'s TIME ends: its SPACE dies")));
+        let summary = match (used, self.span("borrowed for `'static`")) {
+            (Some(used), _) => {
+                notes.push((used, format!("{holder} used after {place}'s SPACE died")));
+                format!(
+                    "{holder} names {place}'s SPACE, but {place}'s TIME ended at line {}, and {holder} is still \
+                     used at line {}.",
+                    dropped.line, used.line,
+                )
+            }
+            (None, Some(forever)) => {
+                notes.push((forever, format!("{holder} must stay valid as long as the program, 'static")));
+                format!(
+                    "{holder} names {place}'s SPACE and must stay valid as long as the program ('static), but \
+                     {place}'s TIME ends at line {}.",
+                    dropped.line,
+                )
+            }
+            (None, None) => format!(
+                "{holder} names {place}'s SPACE for longer than {place}'s TIME, which ends at line {}.",
+                dropped.line,
+            ),
+        };
+        Some(Explanation { summary: opened_by(&holder, summary), notes, section: &DEAD_SPACE })
+    }
+
+    fn temporary_dies(&self) -> Option<Explanation<'a>> {
+        let freed = self.span("freed at the end")?;
+        let used = self.later_use();
+        let holder = holder(self.primary, used);
+        let temporary = &self.primary.snippet;
+        let mut notes = vec![
+            (self.primary, format!("`{temporary}`: SPACE no binding owns")),
+            (freed, "✗ that SPACE dies with the statement".to_string()),
+        ];
+        let until = used.map_or(String::new(), |used| {
+            notes.push((used, format!("{holder} used after that SPACE died")));
+            format!(", but {holder} still names it at line {}", used.line)
+        });
+        let summary = format!(
+            "`{temporary}` at line {} makes SPACE that no binding owns, so its TIME ends with the statement{until}. \
+             Give it an owner first, let owner(..) = take({temporary}), and name that.",
+            self.primary.line,
+        );
+        Some(Explanation { summary, notes, section: &DEAD_SPACE })
+    }
+
+    fn closure_outlives(&self) -> Option<Explanation<'a>> {
+        let place = self.place();
+        let captured = self.span("borrowed here")?;
+        let notes = vec![
+            (self.primary, format!("✗ the closure may outlive {place}'s TIME")),
+            (captured, format!("the closure names {place}'s SPACE")),
+        ];
+        let summary = format!(
+            "The closure at line {} names {place}'s SPACE (line {}), but it may run longer than this function, \
+             and {place}'s SPACE dies when the function returns. With `move`, the closure takes {place} and \
+             owns its SPACE instead.",
+            self.primary.line, captured.line,
+        );
+        Some(Explanation { summary, notes, section: &FEATURES })
+    }
+}
+
+/// `r = coord_exclusive(x)`, or `the closure captures coord_exclusive(x)`.
+fn holds(holder: &str, coord: &str) -> String {
+    if holder == "the closure" {
+        format!("{holder} captures {coord}")
+    } else {
+        format!("{holder} = {coord}")
+    }
+}
+
+/// The same, as the subject of a sentence: `the closure capturing ...`.
+fn subject(holder: &str, coord: &str) -> String {
+    if holder == "the closure" {
+        format!("{holder} capturing {coord}")
+    } else {
+        format!("{holder} = {coord}")
+    }
+}
+
+/// A summary that opens with `holder`: words standing in for a binding, `the
+/// closure`, start the sentence with a capital; a binding named `a` keeps its
+/// case.
+fn opened_by(holder: &str, mut summary: String) -> String {
+    if holder.contains(' ') {
+        summary[..1].make_ascii_uppercase();
+    }
+    summary
+}
+
+/// `r = coord_shared(x)` when that is the whole assignment, `r names x's
+/// SPACE, through coord_shared(x)` when the coordinate is passed on, as in
+/// `r = longest(&x, &y)`, and `r names x's SPACE` for one made by a method
+/// call.
+fn names(holder: &str, place: &str, borrow: &Span) -> String {
+    let value = borrow.source.split_once('=').map(|(_, value)| value.trim().trim_end_matches(';'));
+    match Coord::written(&borrow.snippet) {
+        Some(coord) if value == Some(borrow.snippet.as_str()) => format!("{holder} = {}", coord.notation(place)),
+        Some(coord) => format!("{holder} names {place}'s SPACE, through {}", coord.notation(place)),
+        None => format!("{holder} names {place}'s SPACE"),
+    }
+}
+
+/// The binding that holds the coordinate created at `borrow`: `r` in
+/// `let r = &x;`, `let r: &i32 = &x;` or `r = &x;`. Otherwise the binding
+/// used later, if that is a plain name. A closure holds what it captures.
+fn holder(borrow: &Span, used: Option<&Span>) -> String {
+    if borrow.snippet.starts_with('|') || borrow.snippet.starts_with("move ") {
+        return "the closure".to_string();
+    }
+    let line = borrow.source.trim();
+    let assigned = match line.strip_prefix("let ") {
+        Some(rest) => rest.split(['=', ':']).next(),
+        None => line.split_once('=').filter(|(_, value)| !value.starts_with('=')).map(|(target, _)| target),
+    };
+    let identifier = |text: &str| {
+        let text = text.trim();
+        let text = text.strip_prefix("mut ").unwrap_or(text);
+        let plain = !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_');
+        plain.then(|| text.to_string())
+    };
+    assigned
+        .and_then(identifier)
+        .or_else(|| used.and_then(|used| identifier(&used.snippet)))
+        .unwrap_or_else(|| "a coordinate".to_string())
+}
@@ -0,0 +1,228 @@
+//! Retell rustc's ownership errors in SPACE, TIME and COORDINATES.
+//!
+//! ```text
+//! cargo check --message-format=json | explain
+//! explain DIR [CHECK_ARGS...]
+//! ```
+//!
+//! Reads cargo's JSON messages from stdin, or runs
+//! `cargo check --message-format=json CHECK_ARGS...` in the crate at DIR. For
+//! the code in an exercise's `compile_fail()`:
+//!
+//! ```text
+//! explain exercises/04-lifetimes --bin 02_two_inputs --features compile-fail
+//! ```
+//!
+//! The borrow-check errors E0382, E0499, E0502, E0505, E0506, E0597, E0716
+//! and E0373 are told in course terms: which binding names whose SPACE, in
+//! the notation of `explicit!`, and where that SPACE's TIME ends. Each line
+//! involved is shown with what happens there, then the chapter section that
+//! explains the rule. Other errors are printed as rustc renders them;
+//! warnings are left out.
+
+mod borrowck;
+
+use std::io::Read;
+use std::process::Command;
+use std::{env, io, process};
+
+use serde_json::Value;
+
+/// An error from cargo's JSON output.
+pub struct Diagnostic {
+    /// `E0597`; `None` for errors without a code.
+    pub code: Option<String>,
+    /// `` `x` does not live long enough ``
+    pub message: String,
+    pub spans: Vec<Span>,
+    /// As rustc prints it.
+    pub rendered: String,
+}
+
+/// A place in the source rustc points at.
+pub struct Span {
+    /// Relative to the crate, `src/bin/01_lifetime_validity.rs`.
+    pub file: String,
+    /// 1-based.
+    pub line: usize,
+    pub primary: bool,
+    /// `borrowed value does not live long enough`; empty when there is none.
+    pub label: String,
+    /// The whole source line the span starts on.
+    pub source: String,
+    /// The part of that line the span covers, `&x`.
+    pub snippet: String,
+}
+
+impl Diagnostic {
+    fn primary(&self) -> Option<&Span> {
+        self.spans.iter().find(|span| span.primary)
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let json = match args.split_first() {
+        None => {
+            let mut json = String::new();
+            if let Err(e) = io::stdin().read_to_string(&mut json) {
+                eprintln!("explain: cannot read stdin: {e}");
+                process::exit(1);
+            }
+            json
+        }
+        Some((dir, _)) if dir.starts_with('-') => {
+            eprintln!("usage: explain DIR [CHECK_ARGS...], or pipe in cargo check --message-format=json");
+            process::exit(1);
+        }
+        Some((dir, check_args)) => check(dir, check_args),
+    };
+
+    let diagnostics: Vec<Diagnostic> = json.lines().filter_map(parse).collect();
+    if diagnostics.is_empty() {
+        eprintln!("explain: no errors");
+    }
+    for (i, diagnostic) in diagnostics.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match borrowck::explain(diagnostic) {
+            Some(explanation) => print!("{}", render(diagnostic, &explanation)),
+            None => print!("{}", diagnostic.rendered),
+        }
+    }
+}
+
+/// `cargo check` the crate at `dir`; its JSON messages.
+fn check(dir: &str, check_args: &[String]) -> String {
+    let output = Command::new("cargo")
+        .args(["check", "--quiet", "--message-format=json"])
+        .args(check_args)
+        .current_dir(dir)
+        .output()
+        .unwrap_or_else(|e| {
+            eprintln!("explain: cannot run cargo in {dir}: {e}");
+            process::exit(1);
+        });
+    // cargo failed before rustc had anything to say: a bad argument or manifest
+    if !output.status.success() && output.stdout.is_empty() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        process::exit(1);
+    }
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// An error in a `compiler-message` line of cargo's JSON output. Warnings,
+/// and the `aborting due to` summary that points nowhere, are skipped.
+fn parse(line: &str) -> Option<Diagnostic> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value["reason"] != "compiler-message" || value["message"]["level"] != "error" {
+        return None;
+    }
+    let message = &value["message"];
+    let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+    let spans: Vec<Span> = message["spans"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|span| {
+            let first = &span["text"][0];
+            let source = text(&first["text"]);
+            // 1-based character columns of the highlighted part
+            let start = first["highlight_start"].as_u64().unwrap_or(1) as usize;
+            let end = first["highlight_end"].as_u64().unwrap_or(1) as usize;
+            let snippet = source.chars().skip(start.saturating_sub(1)).take(end.saturating_sub(start)).collect();
+            Some(Span {
+                file: span["file_name"].as_str()?.to_string(),
+                line: span["line_start"].as_u64()? as usize,
+                primary: span["is_primary"].as_bool().unwrap_or(false),
+                label: text(&span["label"]),
+                source,
+                snippet,
+            })
+        })
+        .collect();
+    if spans.is_empty() {
+        return None;
+    }
+    Some(Diagnostic {
+        code: message["code"]["code"].as_str().map(String::from),
+        message: text(&message["message"]),
+        spans,
+        rendered: text(&message["rendered"]),
+    })
+}
+
+/// The error's heading, the explanation, and each line it involves with
+/// what happens there:
+///
+/// ```text
+/// error[E0597]: `x` does not live long enough
+///   --> src/main.rs:5
+///   r names x's SPACE, but x's TIME ended at line 6, and r is still used
+///   at line 7.
+///
+///   4 |     let x = 5;      owner(x): its SPACE starts
+///   5 |     r = &x;         r = coord_shared(x)
+///   ...
+///
+///   see "Detecting Dead Space": course/ch03-how-compilers-track-references.md#detecting-dead-space
+/// ```
+fn render(diagnostic: &Diagnostic, explanation: &borrowck::Explanation) -> String {
+    let code = diagnostic.code.as_deref().unwrap_or_default();
+    let mut text = format!("error[{code}]: {}\n", diagnostic.message);
+    if let Some(primary) = diagnostic.primary() {
+        text.push_str(&format!("  --> {}:{}\n", primary.file, primary.line));
+    }
+    for line in wrap(&explanation.summary, 76) {
+        text.push_str(&format!("  {line}\n"));
+    }
+
+    // One row per line, in source order; notes on the same line are joined
+    let mut rows: Vec<(usize, &str, Vec<&str>)> = Vec::new();
+    for (span, note) in &explanation.notes {
+        match rows.iter_mut().find(|(line, _, _)| *line == span.line) {
+            Some((_, _, notes)) => notes.push(note),
+            None => rows.push((span.line, span.source.trim_end(), vec![note])),
+        }
+    }
+    rows.sort_by_key(|(line, _, _)| *line);
+    if !rows.is_empty() {
+        let indent = rows
+            .iter()
+            .filter(|(_, source, _)| !source.trim().is_empty())
+            .map(|(_, source, _)| source.len() - source.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let number = rows.iter().map(|(line, _, _)| line.to_string().len()).max().unwrap_or(0);
+        let width = rows.iter().map(|(_, source, _)| source.chars().count().saturating_sub(indent)).max().unwrap_or(0);
+        text.push('\n');
+        for (line, source, notes) in &rows {
+            let source = source.get(indent..).unwrap_or_default();
+            text.push_str(&format!("  {line:>number$} |   {source:<width$}   {}\n", notes.join("; ")));
+        }
+    }
+
+    let section = explanation.section;
+    text.push_str(&format!("\n  see {:?}: {}\n", section.title, section.link()));
+    text
+}
+
+/// `text` split into lines of at most `width` characters, at spaces.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
@@ -0,0 +1,36 @@
+// Each bin of tests/fixture has one borrow-check error. explain checks it the
+// way a learner would run it, so a label rustc rewords shows up here as an
+// error told in rustc's words instead of the course's.
+//
+// EXPLAIN_BLESS=1 writes what explain prints to tests/expected.
+
+use std::path::Path;
+use std::process::Command;
+use std::{env, fs};
+
+const CODES: [&str; 8] = ["e0382", "e0499", "e0502", "e0505", "e0506", "e0597", "e0716", "e0373"];
+
+#[test]
+fn fixture() {
+    let tests = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("fixture");
+    let bless = env::var_os("EXPLAIN_BLESS").is_some();
+    let mut differ = Vec::new();
+    for code in CODES {
+        let output = Command::new(env!("CARGO_BIN_EXE_explain"))
+            .arg(tests.join("fixture"))
+            .args(["--bin", code])
+            .env("CARGO_TARGET_DIR", &target)
+            .output()
+            .expect("explain runs");
+        let explained = String::from_utf8(output.stdout).expect("explain prints UTF-8");
+        let expected = tests.join("expected").join(format!("{code}.txt"));
+        if bless {
+            fs::write(&expected, &explained).expect("expected output is writable");
+        } else if fs::read_to_string(&expected).ok().as_deref() != Some(explained.as_str()) {
+            eprintln!("{code}: explain printed\n{explained}");
+            differ.push(code);
+        }
+    }
+    assert!(differ.is_empty(), "explain's output differs from tests/expected for {differ:?}");
+}
@@ -0,0 +1,9 @@
+use std::thread;
+
+fn main() {
+    let x = 5;
+    let handle = thread::spawn(|| {
+        println!("{}", x);
+    });
+    handle.join().unwrap();
+}
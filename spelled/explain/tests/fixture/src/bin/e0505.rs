@@ -0,0 +1,8 @@
+fn consume(_: String) {}
+
+fn main() {
+    let s = String::from("hello");
+    let r = &s;
+    consume(s);
+    println!("{}", r);
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let s = String::from("hello").as_str();
+    println!("{}", s);
+}
@@ -0,0 +1,643 @@
+//! Reverse of `explicit!`: rewrite ordinary Rust `let` statements into notation.
+//!
+//! ```text
+//! spell [FILE]
+//! ```
+//!
+//! Reads FILE (or stdin) and prints it back with every `let` rewritten:
+//!
+//! - `&x` → `coord_shared(x)`, `&mut x` → `coord_exclusive(x)`
+//! - `*r` → `at(r)`
+//! - `Box::new(y)` → `coord_heap(y)`
+//! - `Rc::new(y)` / `Arc::new(y)` → `coord_counted(y)` / `coord_counted_atomic(y)`
+//! - `Rc::clone(&a)` → `share_owner(a)`, `Rc::downgrade(&a)` → `coord_weak(a)`
+//! - `w.upgrade()` → `try_at(w)`
+//! - `c.borrow()` / `c.borrow_mut()` → `coord_shared_runtime(c)` / `coord_exclusive_runtime(c)`
+//! - `m.lock().unwrap()` / `rw.read().unwrap()` → `coord_exclusive_locked(m)` / `coord_shared_locked(rw)`
+//! - `y.clone()` → `clone_copy(y)`
+//! - `let x = y` → `let owner(x) = take_or_mem_copy(y)`
+//! - `let mut x` → `owner(rebindable(x))` or `name(rebindable(x))`
+//! - `let a = Rc::new(y)` → `let co_owner(a) = coord_counted(y)`
+//! - `let g = c.borrow()` → `let guard(g) = coord_shared_runtime(c)`
+//! - `let u = w.upgrade()` → `let co_owner(u) = try_at(w)`
+//! - `let Point { x, .. } = p` → `let Point { x: owner(x), .. } = take_or_mem_copy(p)`
+//!
+//! Whether a binding is an `owner` or a `name` often depends on types `syn`
+//! cannot see. Those lines are annotated with a `// ?` comment asking the
+//! question, and default to `owner`.
+
+use std::io::Read;
+use std::ops::Range;
+use std::{env, fs, io, process};
+
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Expr, Local, Pat, Type, UnOp};
+
+fn main() {
+    let source = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("spell: cannot read {path}: {e}");
+            process::exit(1);
+        }),
+        None => {
+            let mut source = String::new();
+            if let Err(e) = io::stdin().read_to_string(&mut source) {
+                eprintln!("spell: cannot read stdin: {e}");
+                process::exit(1);
+            }
+            source
+        }
+    };
+
+    match spell(&source) {
+        Ok(spelled) => print!("{spelled}"),
+        Err(e) => {
+            let start = e.span().start();
+            eprintln!("spell: {}:{}: {e}", start.line, start.column + 1);
+            process::exit(1);
+        }
+    }
+}
+
+/// Rewrite every `let` in a whole file, or in a snippet of statements.
+fn spell(source: &str) -> syn::Result<String> {
+    let edits = match syn::parse_file(source) {
+        Ok(file) => {
+            // parse_file strips a BOM and shebang line before lexing
+            let bom = if source.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+            let shebang = file.shebang.as_ref().map_or(0, String::len);
+            let mut speller = Speller::new(source, bom + shebang);
+            speller.visit_file(&file);
+            speller.edits
+        }
+        Err(file_error) => {
+            let stmts = syn::Block::parse_within
+                .parse_str(source)
+                .map_err(|_| file_error)?;
+            let mut speller = Speller::new(source, 0);
+            for stmt in &stmts {
+                speller.visit_stmt(stmt);
+            }
+            speller.edits
+        }
+    };
+    Ok(apply(source, edits))
+}
+
+/// A text replacement. Insertions have an empty range.
+///
+/// Edits at the same position are ordered by rank: closing parens (negative,
+/// innermost first), then replacements (zero), then opening text (positive,
+/// outermost first).
+struct Edit {
+    range: Range<usize>,
+    text: String,
+    rank: i32,
+}
+
+fn apply(source: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|edit| (edit.range.start, edit.rank));
+    let mut spelled = String::with_capacity(source.len());
+    let mut cursor = 0;
+    for edit in edits {
+        if edit.range.start >= cursor {
+            spelled.push_str(&source[cursor..edit.range.start]);
+            cursor = edit.range.start;
+        }
+        spelled.push_str(&edit.text);
+        cursor = cursor.max(edit.range.end);
+    }
+    spelled.push_str(&source[cursor..]);
+    spelled
+}
+
+/// What the binding on the left side receives.
+enum Kind {
+    Owner,
+    CoOwner,
+    Guard,
+    Name { exclusive: bool },
+    /// Needs type information; the string is the question to ask.
+    Unknown(String),
+}
+
+impl Kind {
+    fn marker(&self) -> &'static str {
+        match self {
+            Kind::Name { .. } => "name",
+            Kind::CoOwner => "co_owner",
+            Kind::Guard => "guard",
+            Kind::Owner | Kind::Unknown(_) => "owner",
+        }
+    }
+
+    fn of_type(ty: &Type) -> Kind {
+        match ty {
+            Type::Reference(reference) => Kind::Name {
+                exclusive: reference.mutability.is_some(),
+            },
+            Type::Paren(paren) => Kind::of_type(&paren.elem),
+            Type::Path(path) if path.path.segments.last().is_some_and(|seg| seg.ident == "Rc" || seg.ident == "Arc") => {
+                Kind::CoOwner
+            }
+            _ => Kind::Owner,
+        }
+    }
+}
+
+/// Methods whose result is new SPACE rather than coordinates into the receiver.
+const OWNING_METHODS: &[&str] = &["clone", "to_string", "to_owned", "to_vec", "into", "collect", "len"];
+
+/// Types whose SPACE has counted owner tags.
+const COUNTED: &[&str] = &["Rc", "Arc"];
+
+/// Associated functions that construct a new value.
+const CONSTRUCTORS: &[&str] = &["new", "from", "default", "with_capacity", "clone"];
+
+struct Speller<'src> {
+    source: &'src str,
+    /// Byte offset of the parsed text within `source`.
+    offset: usize,
+    edits: Vec<Edit>,
+}
+
+impl<'ast> Visit<'ast> for Speller<'_> {
+    fn visit_local(&mut self, local: &'ast Local) {
+        self.spell_local(local);
+        visit::visit_local(self, local);
+    }
+}
+
+impl<'src> Speller<'src> {
+    fn new(source: &'src str, offset: usize) -> Self {
+        Speller {
+            source,
+            offset,
+            edits: Vec::new(),
+        }
+    }
+
+    fn range(&self, node: &impl Spanned) -> Range<usize> {
+        let range = node.span().byte_range();
+        range.start + self.offset..range.end + self.offset
+    }
+
+    fn text(&self, node: &impl Spanned) -> &'src str {
+        &self.source[self.range(node)]
+    }
+
+    fn insert(&mut self, at: usize, text: impl Into<String>, rank: i32) {
+        self.edits.push(Edit {
+            range: at..at,
+            text: text.into(),
+            rank,
+        });
+    }
+
+    fn replace(&mut self, range: Range<usize>, text: impl Into<String>) {
+        self.edits.push(Edit {
+            range,
+            text: text.into(),
+            rank: 0,
+        });
+    }
+
+    fn wrap(&mut self, node: &impl Spanned, open: &str, close: &str, depth: i32) {
+        let range = self.range(node);
+        self.insert(range.start, open, depth);
+        self.insert(range.end, close, -depth);
+    }
+
+    fn spell_local(&mut self, local: &Local) {
+        // let-else has no notation; leave it as written
+        if local.init.as_ref().is_some_and(|init| init.diverge.is_some()) {
+            return;
+        }
+        let mut questions = Vec::new();
+        let init = local.init.as_ref().map(|init| &*init.expr);
+        self.spell_binding(&local.pat, init, &mut questions);
+        if !questions.is_empty() {
+            let end = self.range(local).end;
+            self.insert(end, format!(" // ? {}", questions.join("; ")), i32::MAX);
+        }
+    }
+
+    fn spell_binding(&mut self, pat: &Pat, init: Option<&Expr>, questions: &mut Vec<String>) {
+        match (pat, init) {
+            // (a, b) = (x, &y) → pairwise
+            (Pat::Tuple(pats), Some(Expr::Tuple(exprs))) if pats.elems.len() == exprs.elems.len() => {
+                for (pat, expr) in pats.elems.iter().zip(&exprs.elems) {
+                    self.spell_binding(pat, Some(expr), questions);
+                }
+            }
+            (Pat::Paren(paren), _) => self.spell_binding(&paren.pat, init, questions),
+            // let _ = x; neither takes nor copies x
+            (Pat::Wild(_), _) => {
+                if let Some(init) = init {
+                    self.spell_expr(init, 1);
+                }
+            }
+            _ => {
+                let kind = match (pat, init) {
+                    (Pat::Type(typed), _) if matches!(*typed.pat, Pat::Ident(_)) => Kind::of_type(&typed.ty),
+                    (_, Some(init)) => self.kind_of(init),
+                    (_, None) => Kind::Unknown("owner or name: declared without a value".into()),
+                };
+                if let Kind::Unknown(question) = &kind {
+                    questions.push(question.clone());
+                }
+                self.spell_pat(pat, &kind, questions);
+                if let Some(init) = init {
+                    self.spell_init(init, &kind);
+                }
+            }
+        }
+    }
+
+    /// Decide owner or name from the right side alone.
+    fn kind_of(&self, expr: &Expr) -> Kind {
+        match expr {
+            Expr::Reference(reference) => Kind::Name {
+                exclusive: reference.mutability.is_some(),
+            },
+            Expr::Paren(paren) => self.kind_of(&paren.expr),
+            Expr::Lit(_)
+            | Expr::Macro(_)
+            | Expr::Struct(_)
+            | Expr::Array(_)
+            | Expr::Repeat(_)
+            | Expr::Tuple(_)
+            | Expr::Binary(_)
+            | Expr::Range(_)
+            | Expr::Cast(_)
+            | Expr::Unary(_) => Kind::Owner,
+            Expr::Call(call) if COUNTED.iter().any(|ty| is_path(&call.func, &[ty, "downgrade"])) => {
+                Kind::Name { exclusive: false }
+            }
+            Expr::Call(call) if COUNTED.iter().any(|ty| is_path(&call.func, &[ty, "new"]) || is_path(&call.func, &[ty, "clone"])) => {
+                Kind::CoOwner
+            }
+            // An owner tag, if the SPACE is still alive
+            Expr::MethodCall(call) if call.method == "upgrade" && call.args.is_empty() => Kind::CoOwner,
+            Expr::MethodCall(_) if runtime_op(expr).is_some() => Kind::Guard,
+            Expr::Call(call) if is_constructor(&call.func) => Kind::Owner,
+            Expr::MethodCall(call) if OWNING_METHODS.iter().any(|m| call.method == m) => Kind::Owner,
+            Expr::Path(_) | Expr::Field(_) | Expr::Index(_) => {
+                Kind::Unknown(format!("owner or name: is `{}` a reference?", self.text(expr)))
+            }
+            Expr::Call(call) => Kind::Unknown(format!(
+                "owner or name: does `{}` return a reference?",
+                self.text(&call.func)
+            )),
+            Expr::MethodCall(call) => Kind::Unknown(format!(
+                "owner or name: does `.{}()` return a reference?",
+                call.method
+            )),
+            _ => Kind::Unknown("owner or name: does this expression produce a reference?".into()),
+        }
+    }
+
+    fn spell_pat(&mut self, pat: &Pat, kind: &Kind, questions: &mut Vec<String>) {
+        match pat {
+            Pat::Ident(ident) if ident.by_ref.is_some() || ident.subpat.is_some() => {
+                questions.push(format!("`{}` has no notation yet", self.text(pat)));
+            }
+            Pat::Ident(ident) => {
+                let marker = kind.marker();
+                match &ident.mutability {
+                    // mut x → owner(rebindable(x))
+                    Some(mutability) => {
+                        let start = self.range(mutability).start;
+                        let ident_range = self.range(&ident.ident);
+                        self.replace(start..ident_range.start, format!("{marker}(rebindable("));
+                        self.insert(ident_range.end, "))", -1);
+                    }
+                    // x → owner(x)
+                    None => self.wrap(&ident.ident, &format!("{marker}("), ")", 1),
+                }
+            }
+            Pat::Type(typed) => self.spell_pat(&typed.pat, kind, questions),
+            Pat::Paren(paren) => self.spell_pat(&paren.pat, kind, questions),
+            Pat::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.spell_pat(elem, kind, questions);
+                }
+            }
+            // Some(x) → Some(owner(x)), [first, ..] → [owner(first), ..]
+            Pat::TupleStruct(ts) => {
+                for elem in &ts.elems {
+                    self.spell_pat(elem, kind, questions);
+                }
+            }
+            Pat::Slice(slice) => {
+                for elem in &slice.elems {
+                    self.spell_pat(elem, kind, questions);
+                }
+            }
+            Pat::Struct(st) => {
+                for field in &st.fields {
+                    // Point { x } → Point { x: owner(x) }
+                    if field.colon_token.is_none() {
+                        let start = self.range(field).start;
+                        self.insert(start, format!("{}: ", self.text(&field.member)), 0);
+                    }
+                    self.spell_pat(&field.pat, kind, questions);
+                }
+            }
+            Pat::Wild(_) | Pat::Rest(_) => {}
+            _ => questions.push(format!("`{}` has no notation yet", self.text(pat))),
+        }
+    }
+
+    /// The right side of a `let`: places are taken, copied, or re-borrowed.
+    fn spell_init(&mut self, expr: &Expr, kind: &Kind) {
+        match expr {
+            Expr::Path(_) | Expr::Field(_) | Expr::Index(_) => {
+                // A name cannot receive take_or_mem_copy; re-borrow the target instead
+                let (open, close) = match kind {
+                    Kind::Name { exclusive: false } => ("coord_shared(at(", "))"),
+                    Kind::Name { exclusive: true } => ("coord_exclusive(at(", "))"),
+                    Kind::Owner | Kind::CoOwner | Kind::Guard | Kind::Unknown(_) => ("take_or_mem_copy(", ")"),
+                };
+                self.wrap(expr, open, close, 1);
+                self.spell_expr(expr, 2);
+            }
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.spell_init(elem, &Kind::Owner);
+                }
+            }
+            Expr::Array(array) => {
+                for elem in &array.elems {
+                    self.spell_init(elem, &Kind::Owner);
+                }
+            }
+            _ => self.spell_expr(expr, 1),
+        }
+    }
+
+    /// Rewrite the vocabulary inside an expression, without entering blocks.
+    fn spell_expr(&mut self, expr: &Expr, depth: i32) {
+        let range = self.range(expr);
+        // c.borrow() → coord_shared_runtime(c), m.lock().unwrap() → coord_exclusive_locked(m)
+        if let Some((op, cell)) = runtime_op(expr) {
+            self.insert(range.start, format!("{op}("), depth);
+            self.replace(self.range(cell).end..range.end, ")");
+            return self.spell_expr(cell, depth + 1);
+        }
+        match expr {
+            // &x → coord_shared(x), &mut x → coord_exclusive(x)
+            Expr::Reference(reference) => {
+                let op = if reference.mutability.is_some() { "coord_exclusive(" } else { "coord_shared(" };
+                self.replace(range.start..self.range(&reference.expr).start, op);
+                self.insert(range.end, ")", -depth);
+                self.spell_expr(&reference.expr, depth + 1);
+            }
+            // *r → at(r)
+            Expr::Unary(unary) if matches!(unary.op, UnOp::Deref(_)) => {
+                self.replace(range.start..self.range(&unary.expr).start, "at(");
+                self.insert(range.end, ")", -depth);
+                self.spell_expr(&unary.expr, depth + 1);
+            }
+            // Box::new(y) → coord_heap(y)
+            Expr::Call(call) if is_path(&call.func, &["Box", "new"]) => {
+                self.replace(self.range(&call.func), "coord_heap");
+                for arg in &call.args {
+                    self.spell_expr(arg, depth + 1);
+                }
+            }
+            // Rc::new(y) → coord_counted(y), Arc::new(y) → coord_counted_atomic(y)
+            Expr::Call(call) if COUNTED.iter().any(|ty| is_path(&call.func, &[ty, "new"])) => {
+                let op = if is_path(&call.func, &["Rc", "new"]) { "coord_counted" } else { "coord_counted_atomic" };
+                self.replace(self.range(&call.func), op);
+                for arg in &call.args {
+                    self.spell_expr(arg, depth + 1);
+                }
+            }
+            // Rc::clone(&a) → share_owner(a), Rc::downgrade(&a) → coord_weak(a)
+            Expr::Call(call) if call.args.len() == 1 && counted_op(&call.func).is_some() => {
+                let op = counted_op(&call.func).unwrap_or_default();
+                match &call.args[0] {
+                    Expr::Reference(reference) if reference.mutability.is_none() => {
+                        self.replace(range.start..self.range(&reference.expr).start, format!("{op}("));
+                        self.spell_expr(&reference.expr, depth + 1);
+                    }
+                    arg => {
+                        self.replace(self.range(&call.func), format!("{op}(at"));
+                        self.insert(range.end, ")", -depth);
+                        self.spell_expr(arg, depth + 1);
+                    }
+                }
+            }
+            // w.upgrade() → try_at(w)
+            Expr::MethodCall(call) if call.method == "upgrade" && call.args.is_empty() && call.turbofish.is_none() => {
+                self.insert(range.start, "try_at(", depth);
+                self.replace(self.range(&call.receiver).end..range.end, ")");
+                self.spell_expr(&call.receiver, depth + 1);
+            }
+            // y.clone() → clone_copy(y)
+            Expr::MethodCall(call) if call.method == "clone" && call.args.is_empty() && call.turbofish.is_none() => {
+                self.insert(range.start, "clone_copy(", depth);
+                self.replace(self.range(&call.receiver).end..range.end, ")");
+                self.spell_expr(&call.receiver, depth + 1);
+            }
+            Expr::Call(call) => {
+                for arg in &call.args {
+                    self.spell_expr(arg, depth + 1);
+                }
+            }
+            Expr::MethodCall(call) => {
+                self.spell_expr(&call.receiver, depth + 1);
+                for arg in &call.args {
+                    self.spell_expr(arg, depth + 1);
+                }
+            }
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.spell_expr(elem, depth + 1);
+                }
+            }
+            Expr::Array(array) => {
+                for elem in &array.elems {
+                    self.spell_expr(elem, depth + 1);
+                }
+            }
+            Expr::Paren(paren) => self.spell_expr(&paren.expr, depth + 1),
+            Expr::Field(field) => self.spell_expr(&field.base, depth + 1),
+            Expr::Index(index) => {
+                self.spell_expr(&index.expr, depth + 1);
+                self.spell_expr(&index.index, depth + 1);
+            }
+            Expr::Binary(binary) => {
+                self.spell_expr(&binary.left, depth + 1);
+                self.spell_expr(&binary.right, depth + 1);
+            }
+            Expr::Unary(unary) => self.spell_expr(&unary.expr, depth + 1),
+            Expr::Cast(cast) => self.spell_expr(&cast.expr, depth + 1),
+            _ => {}
+        }
+    }
+}
+
+fn is_path(expr: &Expr, segments: &[&str]) -> bool {
+    match expr {
+        Expr::Path(path) => {
+            path.path.segments.len() == segments.len()
+                && path.path.segments.iter().zip(segments).all(|(seg, name)| seg.ident == name)
+        }
+        _ => false,
+    }
+}
+
+/// The notation for `Rc::clone` / `Rc::downgrade` and their `Arc` twins.
+fn counted_op(func: &Expr) -> Option<&'static str> {
+    COUNTED.iter().find_map(|ty| {
+        if is_path(func, &[ty, "clone"]) {
+            Some("share_owner")
+        } else if is_path(func, &[ty, "downgrade"]) {
+            Some("coord_weak")
+        } else {
+            None
+        }
+    })
+}
+
+/// The runtime coordinate a method chain hands out a guard for, and its receiver.
+fn runtime_op(expr: &Expr) -> Option<(&'static str, &Expr)> {
+    let Expr::MethodCall(call) = expr else {
+        return None;
+    };
+    if !call.args.is_empty() || call.turbofish.is_some() {
+        return None;
+    }
+    match call.method.to_string().as_str() {
+        "borrow" => Some(("coord_shared_runtime", &*call.receiver)),
+        "borrow_mut" => Some(("coord_exclusive_runtime", &*call.receiver)),
+        "unwrap" => match &*call.receiver {
+            Expr::MethodCall(inner) if inner.args.is_empty() && inner.method == "lock" => {
+                Some(("coord_exclusive_locked", &*inner.receiver))
+            }
+            Expr::MethodCall(inner) if inner.args.is_empty() && inner.method == "read" => {
+                Some(("coord_shared_locked", &*inner.receiver))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `Type::new(..)`, `String::from(..)`, `Some(..)`: calls that build a new value.
+fn is_constructor(func: &Expr) -> bool {
+    let Expr::Path(path) = func else {
+        return false;
+    };
+    let Some(last) = path.path.segments.last() else {
+        return false;
+    };
+    let name = last.ident.to_string();
+    CONSTRUCTORS.contains(&name.as_str()) || name.starts_with(char::is_uppercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spelled(source: &str) -> String {
+        spell(source).expect("the snippet parses")
+    }
+
+    #[test]
+    fn references_become_coordinates() {
+        assert_eq!(spelled("let r = &x;"), "let name(r) = coord_shared(x);");
+        assert_eq!(spelled("let r = &mut x;"), "let name(r) = coord_exclusive(x);");
+    }
+
+    #[test]
+    fn deref_becomes_at() {
+        assert_eq!(spelled("let v = *r;"), "let owner(v) = at(r);");
+    }
+
+    #[test]
+    fn box_becomes_coord_heap() {
+        assert_eq!(spelled("let b = Box::new(5);"), "let owner(b) = coord_heap(5);");
+    }
+
+    #[test]
+    fn counted_constructors_bind_co_owners() {
+        assert_eq!(spelled("let a = Rc::new(y);"), "let co_owner(a) = coord_counted(y);");
+        assert_eq!(spelled("let a = Arc::new(y);"), "let co_owner(a) = coord_counted_atomic(y);");
+    }
+
+    #[test]
+    fn counted_clone_and_downgrade() {
+        assert_eq!(spelled("let b = Rc::clone(&a);"), "let co_owner(b) = share_owner(a);");
+        assert_eq!(spelled("let w = Rc::downgrade(&a);"), "let name(w) = coord_weak(a);");
+    }
+
+    #[test]
+    fn upgrade_binds_a_co_owner() {
+        assert_eq!(spelled("let u = w.upgrade();"), "let co_owner(u) = try_at(w);");
+    }
+
+    #[test]
+    fn runtime_borrows_bind_guards() {
+        assert_eq!(spelled("let g = c.borrow();"), "let guard(g) = coord_shared_runtime(c);");
+        assert_eq!(spelled("let g = c.borrow_mut();"), "let guard(g) = coord_exclusive_runtime(c);");
+    }
+
+    #[test]
+    fn locks_bind_guards() {
+        assert_eq!(spelled("let g = m.lock().unwrap();"), "let guard(g) = coord_exclusive_locked(m);");
+        assert_eq!(spelled("let g = rw.read().unwrap();"), "let guard(g) = coord_shared_locked(rw);");
+    }
+
+    #[test]
+    fn clone_becomes_clone_copy() {
+        assert_eq!(spelled("let c = y.clone();"), "let owner(c) = clone_copy(y);");
+    }
+
+    #[test]
+    fn places_are_taken_or_copied() {
+        assert_eq!(
+            spelled("let x = y;"),
+            "let owner(x) = take_or_mem_copy(y); // ? owner or name: is `y` a reference?"
+        );
+    }
+
+    #[test]
+    fn mut_becomes_rebindable() {
+        assert_eq!(spelled("let mut x = 5;"), "let owner(rebindable(x)) = 5;");
+        assert_eq!(spelled("let mut r = &x;"), "let name(rebindable(r)) = coord_shared(x);");
+    }
+
+    #[test]
+    fn annotated_types_decide_the_marker() {
+        assert_eq!(spelled("let r: &i32 = f();"), "let name(r): &i32 = f();");
+        assert_eq!(spelled("let a: Rc<i32> = f();"), "let co_owner(a): Rc<i32> = f();");
+    }
+
+    #[test]
+    fn struct_patterns_spell_every_field() {
+        assert_eq!(
+            spelled("let Point { x, mut y, z: w, .. } = Point::new();"),
+            "let Point { x: owner(x), y: owner(rebindable(y)), z: owner(w), .. } = Point::new();"
+        );
+        assert_eq!(spelled("let Point { x, .. } = &p;"), "let Point { x: name(x), .. } = coord_shared(p);");
+    }
+
+    #[test]
+    fn tuple_struct_and_slice_patterns() {
+        assert_eq!(spelled("let Pair(a, _) = Pair(1, 2);"), "let Pair(owner(a), _) = Pair(1, 2);");
+        assert_eq!(spelled("let [first, .., last] = [1, 2, 3];"), "let [owner(first), .., owner(last)] = [1, 2, 3];");
+    }
+
+    #[test]
+    fn unknown_kinds_ask() {
+        assert_eq!(
+            spelled("let v = lookup(k);"),
+            "let owner(v) = lookup(k); // ? owner or name: does `lookup` return a reference?"
+        );
+    }
+}
@@ -0,0 +1,576 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    token::Paren,
+    Expr, ExprClosure, Lit, Macro, PatIdent, Token, TypeParamBound,
+};
+
+use crate::vocabulary::{self, Binds, Category, Op, OPS};
+
+/// Rewrite the call-then-body forms to attributed expressions syn can parse:
+///
+/// - `capture(..) |args| body` → `#[capture(..)] |args| body`
+/// - `spawn_with(..) { .. }` → `#[spawn_with(..)] { .. }`
+/// - `scoped_spawn(..) { .. }` → `#[scoped_spawn(..)] { .. }`
+///
+/// A call followed by a closure parses as a chain of `|` operators, and a call
+/// followed by a block does not parse at all.
+pub(crate) fn attribute_form(tokens: TokenStream) -> TokenStream {
+    let mut rewritten = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if Form::of(&ident).is_some() => {
+                let list = tokens.next_if(|next| {
+                    matches!(next, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)
+                });
+                let body_follows = match (Form::of(&ident), tokens.peek()) {
+                    (Some(Form::Closure), Some(TokenTree::Punct(punct))) => punct.as_char() == '|',
+                    (Some(Form::Closure), Some(TokenTree::Ident(next))) => next == "move",
+                    (Some(_), Some(TokenTree::Group(group))) => group.delimiter() == Delimiter::Brace,
+                    _ => false,
+                };
+                match list {
+                    Some(list) if body_follows => {
+                        let mut pound = Punct::new('#', Spacing::Alone);
+                        pound.set_span(ident.span());
+                        rewritten.push(TokenTree::Punct(pound));
+                        rewritten.push(TokenTree::Group(Group::new(
+                            Delimiter::Bracket,
+                            [TokenTree::Ident(ident), list].into_iter().collect(),
+                        )));
+                    }
+                    list => {
+                        rewritten.push(TokenTree::Ident(ident));
+                        rewritten.extend(list.map(recurse));
+                    }
+                }
+            }
+            other => rewritten.push(recurse(other)),
+        }
+    }
+    rewritten.into_iter().collect()
+}
+
+fn recurse(token: TokenTree) -> TokenTree {
+    match token {
+        TokenTree::Group(group) => {
+            let mut inner = Group::new(group.delimiter(), attribute_form(group.stream()));
+            inner.set_span(group.span());
+            TokenTree::Group(inner)
+        }
+        other => other,
+    }
+}
+
+/// Which form a capture list belongs to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Form {
+    /// `capture(..) |args| body`
+    Closure,
+    /// `spawn_with(..) { .. }`: `std::thread::spawn`
+    Spawn,
+    /// `scoped_spawn(..) { .. }`: `std::thread::scope`
+    Scoped,
+}
+
+impl Form {
+    fn of(ident: &Ident) -> Option<Form> {
+        match ident.to_string().as_str() {
+            "capture" => Some(Form::Closure),
+            "spawn_with" => Some(Form::Spawn),
+            "scoped_spawn" => Some(Form::Scoped),
+            _ => None,
+        }
+    }
+}
+
+/// `take(x), coord_shared(y); Send + 'static`, or for `scoped_spawn` an
+/// optional scope handle first: `s, coord_shared(y)`.
+struct List {
+    scope: Option<Ident>,
+    captures: Vec<Capture>,
+    send: bool,
+    is_static: bool,
+}
+
+struct Capture {
+    op: Ident,
+    name: Ident,
+    span: Span,
+}
+
+impl Capture {
+    fn op(&self) -> &'static Op {
+        vocabulary::lookup(&self.op.to_string()).expect("captures are parsed from the vocabulary")
+    }
+
+    /// Whether the capture names SPACE the closure does not own.
+    fn borrows(&self) -> bool {
+        self.op().rewrite.coordinates().is_some()
+    }
+
+    /// Whether an owned capture of a `name(..)` binding puts its coordinates
+    /// in the closure: everything but a new owner tag or weak coordinates.
+    fn moves_coordinates(&self, names: &[Ident]) -> bool {
+        names.contains(&self.name) && matches!(self.op().binds, Binds::Owner | Binds::Moved)
+    }
+}
+
+impl Parse for List {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut scope = None;
+        if input.peek(syn::Ident) && !input.peek2(Paren) {
+            scope = Some(input.parse()?);
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let mut captures = Vec::new();
+        while !input.is_empty() && !input.peek(Token![;]) {
+            let op: Ident = input.parse()?;
+            if !vocabulary::lookup(&op.to_string()).is_some_and(|op| op.captures) {
+                let known: Vec<_> = OPS.iter().filter(|op| op.captures).map(|op| format!("{}(..)", op.name)).collect();
+                return Err(syn::Error::new(
+                    op.span(),
+                    format!("`{op}` cannot capture: a capture is one of {}", known.join(", ")),
+                ));
+            }
+            let content;
+            let parens = parenthesized!(content in input);
+            let name: Ident = content.parse().map_err(|_| {
+                syn::Error::new(content.span(), "a capture names one binding: write `take(x)`, not `take(x.field)`")
+            })?;
+            if !content.is_empty() {
+                return Err(content.error("a capture names one binding: write `take(x)`, not `take(x.field)`"));
+            }
+            let span = op.span().join(parens.span.close()).unwrap_or(op.span());
+            captures.push(Capture { op, name, span });
+            if !input.is_empty() && !input.peek(Token![;]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let (mut send, mut is_static) = (false, false);
+        if input.parse::<Option<Token![;]>>()?.is_some() {
+            let bounds = Punctuated::<TypeParamBound, Token![+]>::parse_separated_nonempty(input)?;
+            for bound in &bounds {
+                match bound {
+                    TypeParamBound::Trait(bound) if bound.path.is_ident("Send") => send = true,
+                    TypeParamBound::Lifetime(lifetime) if lifetime.ident == "static" => is_static = true,
+                    other => {
+                        return Err(syn::Error::new(other.span(), "a closure can be required to be `Send` or `'static`"))
+                    }
+                }
+            }
+        }
+        Ok(List { scope, captures, send, is_static })
+    }
+}
+
+/// Expands closures with a capture list, and the thread forms built on them.
+///
+/// `#[capture(take(x), coord_shared(y))] |args| body` becomes
+///
+/// ```text
+/// { let owner(x) = take(x); let name(y) = coord_shared(y); move |args| body }
+/// ```
+///
+/// so the bindings go through the same checks and rewrites as any other
+/// `let`. `spawn_with` hands that closure to `std::thread::spawn`, and
+/// `scoped_spawn` to a `std::thread::scope`. Runs before `ExplicitTransformer`.
+#[derive(Default)]
+pub(crate) struct Captures {
+    pub(crate) errors: Vec<syn::Error>,
+    /// `name(..)` bindings in scope: they hold coordinates whatever their type.
+    names: Vec<Ident>,
+    /// Every local binding in scope: what a closure can capture. Other names,
+    /// functions and statics, are not captures.
+    locals: Vec<Ident>,
+}
+
+impl Captures {
+    /// For a function body: its parameters are bindings in scope.
+    pub(crate) fn in_fn(sig: &syn::Signature) -> Self {
+        let mut captures = Captures::default();
+        for input in &sig.inputs {
+            if let syn::FnArg::Typed(typed) = input {
+                captures.locals.extend(bindings(&typed.pat));
+            }
+        }
+        captures
+    }
+
+    /// Visit `visit` with the bindings of `pat` in scope.
+    fn scoped(&mut self, pat: &syn::Pat, visit: impl FnOnce(&mut Self)) {
+        let outer = self.locals.len();
+        self.locals.extend(bindings(pat));
+        visit(self);
+        self.locals.truncate(outer);
+    }
+}
+
+impl VisitMut for Captures {
+    fn visit_block_mut(&mut self, block: &mut syn::Block) {
+        let (names, locals) = (self.names.len(), self.locals.len());
+        visit_mut::visit_block_mut(self, block);
+        self.names.truncate(names);
+        self.locals.truncate(locals);
+    }
+
+    fn visit_arm_mut(&mut self, arm: &mut syn::Arm) {
+        let pat = arm.pat.clone();
+        self.scoped(&pat, |this| visit_mut::visit_arm_mut(this, arm));
+    }
+
+    fn visit_expr_for_loop_mut(&mut self, for_loop: &mut syn::ExprForLoop) {
+        self.visit_expr_mut(&mut for_loop.expr);
+        let pat = (*for_loop.pat).clone();
+        self.scoped(&pat, |this| this.visit_block_mut(&mut for_loop.body));
+    }
+
+    fn visit_expr_closure_mut(&mut self, closure: &mut ExprClosure) {
+        let outer = self.locals.len();
+        for input in &closure.inputs {
+            self.locals.extend(bindings(input));
+        }
+        visit_mut::visit_expr_closure_mut(self, closure);
+        self.locals.truncate(outer);
+    }
+
+    // `if let` and `while let` bind for their body only
+    fn visit_expr_if_mut(&mut self, expr_if: &mut syn::ExprIf) {
+        let outer = self.locals.len();
+        self.visit_expr_mut(&mut expr_if.cond);
+        self.visit_block_mut(&mut expr_if.then_branch);
+        self.locals.truncate(outer);
+        if let Some((_, else_branch)) = &mut expr_if.else_branch {
+            self.visit_expr_mut(else_branch);
+        }
+    }
+
+    fn visit_expr_while_mut(&mut self, expr_while: &mut syn::ExprWhile) {
+        let outer = self.locals.len();
+        visit_mut::visit_expr_while_mut(self, expr_while);
+        self.locals.truncate(outer);
+    }
+
+    fn visit_expr_let_mut(&mut self, expr_let: &mut syn::ExprLet) {
+        visit_mut::visit_expr_let_mut(self, expr_let);
+        self.locals.extend(bindings(&expr_let.pat));
+    }
+
+    fn visit_local_mut(&mut self, local: &mut syn::Local) {
+        visit_mut::visit_local_mut(self, local);
+
+        // a later binding of the same name shadows the coordinates
+        let bound = bindings(&local.pat);
+        self.names.retain(|name| !bound.contains(name));
+        self.locals.extend(bound);
+        let pat = match &local.pat {
+            syn::Pat::Type(typed) => &*typed.pat,
+            pat => pat,
+        };
+        if let syn::Pat::TupleStruct(ts) = pat {
+            if crate::is_path_ident(&ts.path, "name") {
+                if let Some((ident, _)) = crate::marker_binding(ts) {
+                    self.names.push(ident.ident);
+                }
+            }
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+
+        let attrs = match expr {
+            Expr::Closure(closure) => &mut closure.attrs,
+            Expr::Block(block) => &mut block.attrs,
+            _ => return,
+        };
+        let Some((index, form)) = attrs.iter().enumerate().find_map(|(index, attr)| {
+            let form = Form::of(attr.path().get_ident()?)?;
+            Some((index, form))
+        }) else {
+            return;
+        };
+        let attr = attrs.remove(index);
+        let mut list = match attr.parse_args::<List>() {
+            Ok(list) => list,
+            Err(error) => return self.errors.push(error),
+        };
+
+        let closure = match (form, &*expr) {
+            (Form::Closure, Expr::Closure(closure)) => closure.clone(),
+            (_, Expr::Block(block)) => syn::parse_quote_spanned! {block.block.brace_token.span.join()=> || #block },
+            _ => return,
+        };
+        match (form, &list.scope) {
+            (Form::Scoped, _) | (_, None) => {}
+            (_, Some(scope)) => {
+                return self.errors.push(syn::Error::new(
+                    scope.span(),
+                    format!("a capture is `op({scope})`; only scoped_spawn(..) takes a scope handle first"),
+                ))
+            }
+        }
+        if form != Form::Closure && (list.send || list.is_static) {
+            self.errors.push(syn::Error::new(
+                attr.span(),
+                "spawn_with(..) and scoped_spawn(..) already check what a thread needs: drop the `; ..` bounds",
+            ));
+        }
+        list.send |= form != Form::Closure;
+        list.is_static |= form == Form::Spawn;
+
+        self.check_uncaptured(&closure, &list);
+        if list.is_static {
+            self.check_static(&list, form);
+        }
+        let closure = expand(closure, &list);
+        let span = attr.span();
+        *expr = match (form, &list.scope) {
+            (Form::Closure, _) => closure,
+            (Form::Spawn, _) => syn::parse_quote_spanned! {span=> ::std::thread::spawn(#closure) },
+            (Form::Scoped, Some(scope)) => syn::parse_quote_spanned! {span=> #scope.spawn(#closure) },
+            (Form::Scoped, None) => {
+                // a scope of its own: the value is what the thread returns
+                let scope = Ident::new("scope", Span::mixed_site());
+                syn::parse_quote_spanned! {span=>
+                    ::std::thread::scope(|#scope| {
+                        #scope
+                            .spawn(#closure)
+                            .join()
+                            .unwrap_or_else(|panic| ::std::panic::resume_unwind(panic))
+                    })
+                }
+            }
+        };
+    }
+}
+
+/// Bind the captures, check them, and make the closure `move`.
+fn expand(mut closure: ExprClosure, list: &List) -> Expr {
+    let lets = list.captures.iter().map(|capture @ Capture { op, name, span }| {
+        let marker = Ident::new(capture.op().binds.marker().name(), *span);
+        quote_spanned! {*span=> let #marker(#name) = #op(#name); }
+    });
+    let send = list.captures.iter().filter(|_| list.send).map(require_thread_safe);
+    let is_static = list.captures.iter().filter(|_| list.is_static).map(require_static);
+    closure.capture = Some(Token![move](closure.or1_token.span));
+    syn::parse2(quote! {{ #(#lets)* #(#send)* #(#is_static)* #closure }})
+        .expect("capture expansion is a block expression")
+}
+
+impl Captures {
+    /// Reject bindings the closure uses but does not list.
+    fn check_uncaptured(&mut self, closure: &ExprClosure, list: &List) {
+        let mut names = Names::default();
+        for input in &closure.inputs {
+            names.visit_pat(input);
+        }
+        names.visit_expr(&closure.body);
+        for (name, span) in names.used {
+            let local = self.locals.iter().any(|local| *local == name);
+            if !local
+                || names.bound.iter().any(|bound| *bound == name)
+                || list.captures.iter().any(|capture| capture.name == name)
+            {
+                continue;
+            }
+            self.errors.push(syn::Error::new(
+                span,
+                format!(
+                    "`{name}` is not in the capture list: add `take({name})`, `mem_copy({name})` \
+                     or `coord_shared({name})` to the list"
+                ),
+            ));
+        }
+    }
+
+    /// A `'static` closure cannot hold coordinates to SPACE owned by this scope.
+    fn check_static(&mut self, list: &List, form: Form) {
+        for capture @ Capture { op, name, span } in &list.captures {
+            if !capture.borrows() {
+                if capture.moves_coordinates(&self.names) {
+                    self.errors.push(syn::Error::new(*span, moved_coordinates(capture, form)));
+                }
+                continue;
+            }
+            let message = match form {
+                Form::Spawn => format!(
+                    "`{op}({name})` cannot go to a spawn_with(..) thread: the thread may outlive {name}'s SPACE, \
+                     and IDENTITY cannot outlive the SPACE it names. Use scoped_spawn(..), or capture \
+                     `take({name})`, `clone_copy({name})` or `share_owner({name})`"
+                ),
+                _ => format!(
+                    "a `'static` closure cannot capture `{op}({name})`: its coordinates name SPACE that dies \
+                     with this scope. Capture `take({name})`, `clone_copy({name})` or `share_owner({name})` instead"
+                ),
+            };
+            self.errors.push(syn::Error::new(*span, message));
+        }
+    }
+}
+
+/// Checks the capture can be used from another thread, reporting the missing
+/// property in course terms at the capture.
+///
+/// Owned captures and exclusive coordinates move their SPACE's IDENTITY to the
+/// other thread, so the value must be `Send`. Shared coordinates let two
+/// threads name the same SPACE at once, so the SPACE must be `Sync`.
+fn require_thread_safe(capture: &Capture) -> TokenStream {
+    let Capture { op, name, span } = capture;
+    let (message, bound, note) = match (capture.op().rewrite.coordinates(), capture.op().category) {
+        (Some(false), _) => (
+            format!("IDENTITY cannot be shared across threads: `{op}({name})` names `{{Self}}` from two threads, but it is not Sync"),
+            quote! { ::std::marker::Sync },
+            "Cell and RefCell SPACE checks its coordinates without synchronization: \
+             name it through coord_exclusive_locked(..) or coord_shared_locked(..) instead",
+        ),
+        (_, Category::Counted) => (
+            format!("IDENTITY cannot cross threads: `{op}({name})` moves `{{Self}}` to another thread, but it is not Send"),
+            quote! { ::std::marker::Send },
+            "coord_counted(..) SPACE counts its owner tags without atomics: use coord_counted_atomic(..)",
+        ),
+        _ => (
+            format!("IDENTITY cannot cross threads: `{op}({name})` moves `{{Self}}` to another thread, but it is not Send"),
+            quote! { ::std::marker::Send },
+            "a value can cross threads only when every SPACE it owns or names can",
+        ),
+    };
+    // `name` is bound to `&T` / `&mut T` for coordinates; the property is T's
+    let value = if capture.borrows() { quote_spanned! {*span=> &*#name } } else { quote_spanned! {*span=> &#name } };
+    quote_spanned! {*span=> {
+        #[diagnostic::on_unimplemented(message = #message, label = "captured here", note = #note)]
+        trait CrossesThreads {}
+        impl<T: ?Sized + #bound> CrossesThreads for T {}
+        fn crosses_threads<T: ?Sized + CrossesThreads>(_: &T) {}
+        crosses_threads(#value);
+    }}
+}
+
+/// `take(r)` of a `name(r)` in a closure that must be `'static`.
+fn moved_coordinates(Capture { op, name, .. }: &Capture, form: Form) -> String {
+    match form {
+        Form::Spawn => format!(
+            "`{op}({name})` cannot go to a spawn_with(..) thread: {name} is a name, its coordinates go with it, \
+             and the thread may outlive the SPACE they name. Use scoped_spawn(..), or capture the SPACE's owner"
+        ),
+        _ => format!(
+            "a `'static` closure cannot capture `{op}({name})`: {name} is a name, and its coordinates name SPACE \
+             that dies with this scope. Capture the SPACE's owner with `take(..)`, `clone_copy(..)` or \
+             `share_owner(..)` instead"
+        ),
+    }
+}
+
+/// Checks the captured value holds no coordinates into a shorter-lived scope.
+///
+/// An outlives bound is not a trait, so unlike `require_thread_safe` it cannot
+/// carry a message of its own. Captures of `name(..)` bindings are rejected
+/// in course terms before expansion; a value whose type hides coordinates,
+/// `Vec<&str>`, fails here with rustc's "does not live long enough" pointing
+/// at the capture.
+fn require_static(Capture { name, span, .. }: &Capture) -> TokenStream {
+    quote_spanned! {*span=> {
+        fn captured_static<T: ?Sized + 'static>(_: &T) {}
+        captured_static(&#name);
+    }}
+}
+
+/// The names `pat` binds.
+fn bindings(pat: &syn::Pat) -> Vec<Ident> {
+    let mut names = Names::default();
+    names.visit_pat(pat);
+    names.bound
+}
+
+/// Names a closure binds and the bindings it refers to.
+///
+/// Any name bound anywhere in the closure counts as bound throughout, so a
+/// use before a shadowing `let` is not reported. Only lowercase single
+/// identifiers are considered: types, constants and variants are not captures,
+/// and [`Captures`] keeps only the local bindings among them.
+#[derive(Default)]
+struct Names {
+    bound: Vec<Ident>,
+    used: BTreeMap<String, Span>,
+}
+
+impl Names {
+    fn use_name(&mut self, ident: &Ident) {
+        let name = ident.to_string();
+        // `self` is the method's receiver, not a binding to capture
+        if name != "self" && name.starts_with(|c: char| c.is_lowercase() || c == '_') {
+            self.used.entry(name).or_insert(ident.span());
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Names {
+    fn visit_pat_ident(&mut self, pat: &'ast PatIdent) {
+        self.bound.push(pat.ident.clone());
+        visit::visit_pat_ident(self, pat);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // a function or an operation, not a captured binding
+        if !matches!(&*call.func, Expr::Path(_)) {
+            self.visit_expr(&call.func);
+        }
+        for arg in &call.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        if let (None, Some(ident)) = (&path.qself, path.path.get_ident()) {
+            self.use_name(ident);
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        // format-style macros: `println!("{x}", y)`
+        let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) else {
+            return;
+        };
+        for arg in &args {
+            if let Expr::Lit(syn::ExprLit { lit: Lit::Str(format), .. }) = arg {
+                for name in inline_args(&format.value()) {
+                    self.use_name(&Ident::new(&name, format.span()));
+                }
+            }
+            self.visit_expr(arg);
+        }
+    }
+}
+
+/// The bindings a format string names inline, `x` and `y` in `"{x} {y:?}"`.
+fn inline_args(format: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = format;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let end = rest.find(['}', ':']).unwrap_or(rest.len());
+        let name = &rest[..end];
+        if name.starts_with(|c: char| c.is_alphabetic() || c == '_') && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            names.push(name.to_string());
+        }
+        rest = &rest[end..];
+    }
+    names
+}
@@ -0,0 +1,79 @@
+use proc_macro2::Span;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+use syn::Expr;
+
+// Each expansion declares its helper traits inside a block expression, so the
+// claim is checked against the concrete type at the call site and the helpers
+// never collide across statements or `explicit!` invocations.
+
+/// `mem_copy(y)` → `y`, rejected unless the value is `Copy`.
+///
+/// The bound sits on the impl of the copying function rather than on a
+/// function argument: on an argument, rustc would answer a rejected claim
+/// with "consider borrowing here", steering toward copying a reference.
+/// The argument keeps its own span so the error can point at it.
+pub(crate) fn mem_copy(arg: &Expr, span: Span) -> Expr {
+    let arg = quote_spanned! {arg.span()=> (#arg)};
+    syn::parse2(quote_spanned! {span=> {
+        #[diagnostic::on_unimplemented(
+            message = "{Self} is not Copy: use take(...) or clone_copy(...)",
+            label = "mem_copy(...) duplicates bytes, which needs a Copy type"
+        )]
+        // Only named in bounds, which dead-code analysis does not count
+        #[allow(dead_code)]
+        trait MemCopyClaim: Copy {}
+        impl<T: Copy> MemCopyClaim for T {}
+        struct MemCopy<T>(::std::marker::PhantomData<T>);
+        impl<T: MemCopyClaim> MemCopy<T> { fn claim(value: &T) -> T { *value } }
+        MemCopy::claim(&#arg)
+    }})
+    .expect("mem_copy expansion is a block expression")
+}
+
+/// `take(y)` → `y`, rejected when the value is `Copy`.
+///
+/// Uses autoref specialization: for a `Copy` value, method lookup finds
+/// `CopyProbe::verify` first, whose bound can never hold. Everything else
+/// falls through to `MoveProbe::verify` on the extra reference.
+pub(crate) fn take(arg: &Expr, span: Span) -> Expr {
+    syn::parse2(quote_spanned! {span=> {
+        struct TakeClaim<T>(::std::marker::PhantomData<T>);
+        #[diagnostic::on_unimplemented(
+            message = "{Self} is Copy: take(...) leaves the source valid, use mem_copy(...)",
+            label = "nothing is taken here, the bytes are copied"
+        )]
+        #[allow(dead_code)]
+        trait TakeNeedsNonCopy {}
+        // Unused whenever the claim holds
+        #[allow(dead_code)]
+        trait CopyProbe<T> { fn verify(&self) where T: TakeNeedsNonCopy {} }
+        impl<T: Copy> CopyProbe<T> for TakeClaim<T> {}
+        trait MoveProbe { fn verify(&self) {} }
+        impl<T> MoveProbe for &TakeClaim<T> {}
+        fn take_claim<T>(_: &T) -> TakeClaim<T> { TakeClaim(::std::marker::PhantomData) }
+        let taken = #arg;
+        (&take_claim(&taken)).verify();
+        taken
+    }})
+    .expect("take expansion is a block expression")
+}
+
+/// `clone_copy(y)` → `y.clone()`, rejected unless the value is `Clone`.
+///
+/// Calling `.clone()` directly would auto-deref and silently clone a
+/// reference instead, so the bound is stated on the value's own type.
+pub(crate) fn clone_copy(arg: &Expr, span: Span) -> Expr {
+    syn::parse2(quote_spanned! {span=> {
+        #[diagnostic::on_unimplemented(
+            message = "{Self} is not Clone: use take(...) or coord_shared(...)",
+            label = "clone_copy(...) calls .clone(), which needs a Clone type"
+        )]
+        #[allow(dead_code)]
+        trait CloneCopyClaim: Clone {}
+        impl<T: Clone> CloneCopyClaim for T {}
+        fn clone_copy_claim<T: CloneCopyClaim>(value: &T) -> T { value.clone() }
+        clone_copy_claim(&#arg)
+    }})
+    .expect("clone_copy expansion is a block expression")
+}
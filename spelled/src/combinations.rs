@@ -0,0 +1,102 @@
+use syn::{Expr, Pat};
+
+use crate::get_ident_name;
+use crate::vocabulary::{self, Binds, Marker, Op};
+
+/// Check a `let` against the "Valid combinations" table.
+///
+/// Only pairings that contradict the owner/name model are rejected. Right
+/// sides the notation does not describe (literals, constructors, calls) are
+/// left for the compiler to decide.
+pub(crate) fn check(pat: &Pat, expr: &Expr, errors: &mut Vec<syn::Error>) {
+    match (pat, expr) {
+        // (owner(a), name(b)) = (take(x), coord_shared(y)) → check pairwise
+        (Pat::Tuple(pats), Expr::Tuple(exprs)) if pats.elems.len() == exprs.elems.len() => {
+            for (pat, expr) in pats.elems.iter().zip(&exprs.elems) {
+                check(pat, expr, errors);
+            }
+        }
+        (Pat::Paren(paren), _) => check(&paren.pat, expr, errors),
+        (Pat::Type(typed), _) => check(&typed.pat, expr, errors),
+        (_, Expr::Paren(paren)) => check(pat, &paren.expr, errors),
+        _ => {
+            let (Pat::TupleStruct(ts), Expr::Call(call)) = (pat, expr) else {
+                return;
+            };
+            let Some(marker) = crate::marker_of(ts) else {
+                return;
+            };
+            let Some(op) = get_ident_name(&call.func).and_then(|name| vocabulary::lookup(&name)) else {
+                return;
+            };
+            if let Some((why, fix)) = contradiction(marker, op) {
+                let (left, op) = (marker.name(), op.name);
+                let mut error = syn::Error::new_spanned(
+                    pat,
+                    format!("`let {left}(..) = {op}(..)` is not a valid combination: {why}"),
+                );
+                error.combine(syn::Error::new_spanned(
+                    call,
+                    format!("`{op}(..)` cannot be received by `{left}(..)`: {fix}"),
+                ));
+                errors.push(error);
+            }
+        }
+    }
+}
+
+/// Why a marker cannot receive an operation, and what to write instead.
+fn contradiction(marker: Marker, op: &Op) -> Option<(&'static str, &'static str)> {
+    match (marker, op.name) {
+        (Marker::Name, "take") => Some((
+            "a name holds coordinates to SPACE owned elsewhere, but take transfers the owner tag",
+            "write `let owner(..) = take(..)`, or `let name(..) = coord_shared(..)` to name the SPACE instead",
+        )),
+        (Marker::Name, "mem_copy" | "clone_copy" | "take_or_mem_copy") => Some((
+            "a name holds coordinates to SPACE owned elsewhere, but a copy is new SPACE that needs an owner",
+            "write `let owner(..) = ..` for the copy, or `let name(..) = coord_shared(at(..))` to copy coordinates",
+        )),
+        (Marker::Name, "coord_heap") => Some((
+            "coord_heap creates new heap SPACE, and the binding holding its coordinates is that SPACE's owner",
+            "write `let owner(..) = coord_heap(..)`",
+        )),
+        (Marker::Name, "coord_counted" | "coord_counted_atomic") => Some((
+            "counted SPACE is new SPACE, and the binding holding its coordinates holds its first owner tag",
+            "write `let co_owner(..) = ..`",
+        )),
+        (Marker::Name, "share_owner") => Some((
+            "share_owner hands out another owner tag, which keeps the SPACE alive",
+            "write `let co_owner(..) = share_owner(..)`, or `let name(..) = coord_weak(..)` to name it without keeping it alive",
+        )),
+        (Marker::Name, "try_at") => Some((
+            "try_at hands out an owner tag while the SPACE is alive, which keeps it alive",
+            "write `if let Some(co_owner(..)) = try_at(..)`",
+        )),
+        (Marker::Owner, "coord_counted" | "coord_counted_atomic" | "share_owner") => Some((
+            "counted SPACE can have several owner tags, so no single binding is its owner",
+            "write `let co_owner(..) = ..`",
+        )),
+        (Marker::Owner | Marker::CoOwner, "coord_weak") => Some((
+            "weak coordinates do not keep the SPACE alive, so they hold no owner tag",
+            "write `let name(..) = coord_weak(..)`, and `try_at(..)` to get an owner tag back",
+        )),
+        (Marker::CoOwner, "coord_heap") => Some((
+            "coord_heap SPACE has exactly one owner tag",
+            "write `let owner(..) = coord_heap(..)`, or `coord_counted(..)` for SPACE with several owners",
+        )),
+        (Marker::Owner | Marker::CoOwner, _) if op.binds == Binds::Name => Some((
+            "coordinates name SPACE that stays owned by the source, so no owner tag is transferred",
+            "write `let name(..) = ..`, or `let owner(..) = take(..)` to transfer the owner tag",
+        )),
+        (Marker::Owner | Marker::CoOwner | Marker::Name, _) if op.binds == Binds::Guard => Some((
+            "the binding holding a runtime-checked borrow is the borrow: it lasts until the guard is dropped",
+            "write `let guard(..) = ..`",
+        )),
+        // `take` may still move a guard
+        (Marker::Guard, _) if !matches!(op.binds, Binds::Guard | Binds::Moved) => Some((
+            "a guard is a runtime-checked borrow, and only the runtime coordinate forms hand one out",
+            "write `let guard(..) = coord_shared_runtime(..)`, or another runtime form such as `coord_exclusive_locked(..)`",
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,69 @@
+use proc_macro2::Span;
+use quote::quote_spanned;
+use syn::Expr;
+
+// Counted SPACE comes in two flavours, `Rc` and `Arc`, and the notation does
+// not know which one a binding holds. Each expansion declares a local trait
+// implemented for both, so the call resolves on the concrete type and anything
+// else is rejected with a message in notation terms.
+
+/// `share_owner(rc)` → `Rc::clone(&rc)` or `Arc::clone(&rc)`.
+pub(crate) fn share_owner(arg: &Expr, span: Span) -> Expr {
+    syn::parse2(quote_spanned! {span=> {
+        #[diagnostic::on_unimplemented(
+            message = "{Self} is not Rc or Arc: share_owner(...) needs counted SPACE",
+            label = "only coord_counted(...) or coord_counted_atomic(...) SPACE has several owner tags"
+        )]
+        trait ShareOwner { fn share_owner(&self) -> Self; }
+        impl<T: ?Sized> ShareOwner for ::std::rc::Rc<T> {
+            fn share_owner(&self) -> Self { ::std::rc::Rc::clone(self) }
+        }
+        impl<T: ?Sized> ShareOwner for ::std::sync::Arc<T> {
+            fn share_owner(&self) -> Self { ::std::sync::Arc::clone(self) }
+        }
+        ShareOwner::share_owner(&#arg)
+    }})
+    .expect("share_owner expansion is a block expression")
+}
+
+/// `coord_weak(rc)` → `Rc::downgrade(&rc)` or `Arc::downgrade(&rc)`.
+pub(crate) fn coord_weak(arg: &Expr, span: Span) -> Expr {
+    syn::parse2(quote_spanned! {span=> {
+        #[diagnostic::on_unimplemented(
+            message = "{Self} is not Rc or Arc: coord_weak(...) needs counted SPACE",
+            label = "weak coordinates only exist for coord_counted(...) or coord_counted_atomic(...) SPACE"
+        )]
+        trait CoordWeak { type Weak; fn coord_weak(&self) -> Self::Weak; }
+        impl<T: ?Sized> CoordWeak for ::std::rc::Rc<T> {
+            type Weak = ::std::rc::Weak<T>;
+            fn coord_weak(&self) -> Self::Weak { ::std::rc::Rc::downgrade(self) }
+        }
+        impl<T: ?Sized> CoordWeak for ::std::sync::Arc<T> {
+            type Weak = ::std::sync::Weak<T>;
+            fn coord_weak(&self) -> Self::Weak { ::std::sync::Arc::downgrade(self) }
+        }
+        CoordWeak::coord_weak(&#arg)
+    }})
+    .expect("coord_weak expansion is a block expression")
+}
+
+/// `try_at(weak)` → `weak.upgrade()`: an owner tag if the SPACE is still alive.
+pub(crate) fn try_at(arg: &Expr, span: Span) -> Expr {
+    syn::parse2(quote_spanned! {span=> {
+        #[diagnostic::on_unimplemented(
+            message = "{Self} is not a Weak: try_at(...) reads through coord_weak(...) coordinates",
+            label = "use at(...) for coordinates that are always valid"
+        )]
+        trait TryAt { type Owner; fn try_at(&self) -> Option<Self::Owner>; }
+        impl<T: ?Sized> TryAt for ::std::rc::Weak<T> {
+            type Owner = ::std::rc::Rc<T>;
+            fn try_at(&self) -> Option<Self::Owner> { self.upgrade() }
+        }
+        impl<T: ?Sized> TryAt for ::std::sync::Weak<T> {
+            type Owner = ::std::sync::Arc<T>;
+            fn try_at(&self) -> Option<Self::Owner> { self.upgrade() }
+        }
+        TryAt::try_at(&#arg)
+    }})
+    .expect("try_at expansion is a block expression")
+}
@@ -0,0 +1,69 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    visit_mut::VisitMut,
+    Attribute, Block, ImplItemFn, Item, ItemFn, Meta, Signature, TraitItemFn,
+};
+
+use crate::signature::{self, Args};
+
+/// The items of an `explicit_items!` block.
+pub(crate) struct Items(pub(crate) Vec<Item>);
+
+impl Parse for Items {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(Items(items))
+    }
+}
+
+/// Rewrites every function in a run of items the way `#[explicit_fn]`
+/// rewrites one: free functions, methods, associated functions and trait
+/// methods with a default body, inside modules too.
+#[derive(Default)]
+pub(crate) struct Expander {
+    /// Signatures that could not be rewritten. Their items are not emitted.
+    pub(crate) signature_errors: Vec<syn::Error>,
+    pub(crate) errors: Vec<syn::Error>,
+}
+
+impl Expander {
+    fn function(&mut self, attrs: &mut Vec<Attribute>, sig: &mut Signature, body: Option<&mut Block>) {
+        if let Err(error) = take_args(attrs).and_then(|args| signature::expand(&args, sig)) {
+            self.signature_errors.push(error);
+        }
+        if let Some(body) = body {
+            self.errors.extend(crate::explicit_body(sig, body));
+        }
+    }
+}
+
+impl VisitMut for Expander {
+    fn visit_item_fn_mut(&mut self, func: &mut ItemFn) {
+        self.function(&mut func.attrs, &mut func.sig, Some(&mut func.block));
+    }
+
+    fn visit_impl_item_fn_mut(&mut self, func: &mut ImplItemFn) {
+        self.function(&mut func.attrs, &mut func.sig, Some(&mut func.block));
+    }
+
+    fn visit_trait_item_fn_mut(&mut self, func: &mut TraitItemFn) {
+        self.function(&mut func.attrs, &mut func.sig, func.default.as_mut());
+    }
+}
+
+/// Strip `#[explicit_fn(..)]` off a function inside the block; its
+/// `from = ..` applies as it would on a free function.
+fn take_args(attrs: &mut Vec<Attribute>) -> syn::Result<Args> {
+    let Some(index) = attrs.iter().position(|attr| attr.path().is_ident("explicit_fn")) else {
+        return Ok(Args::default());
+    };
+    match attrs.remove(index).meta {
+        Meta::Path(_) => Ok(Args::default()),
+        Meta::List(list) => list.parse_args(),
+        Meta::NameValue(meta) => Err(syn::Error::new(meta.span(), "expected `#[explicit_fn(from = <param>)]`")),
+    }
+}
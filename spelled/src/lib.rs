@@ -1,11 +1,27 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{
     spanned::Spanned,
     Expr, Local, Pat, PatIdent, Token,
     visit_mut::{self, VisitMut},
 };
 
+mod captures;
+mod claims;
+mod combinations;
+mod counted;
+mod items;
+mod proofs;
+mod properties;
+mod report;
+mod signature;
+mod structs;
+mod trace;
+mod types;
+mod vocabulary;
+
+use vocabulary::{Generated, Rewrite};
+
 /// Explicit declaration syntax for learning Rust.
 ///
 /// The ownership model reduces to two things:
@@ -18,28 +34,114 @@ use syn::{
 /// - `owner(rebindable(x))` → `mut x` (owns, can rebind)
 /// - `name(x)` → `x` (names space owned elsewhere)
 /// - `name(rebindable(x))` → `mut x` (names, can rebind to different coordinates)
+/// - `co_owner(x)` → `x` (holds one of several owner tags of counted SPACE)
+/// - `guard(g)` → `g` (the binding is a runtime-checked borrow; dropping it ends the borrow)
+///
+/// Markers work in every pattern position: `let`, match arms, `if let`,
+/// `while let`, `for`, closure and fn parameters, and at any depth inside
+/// tuple, struct, enum, slice, `&pat` and `x @ pat` patterns:
+///
+/// - `let Point { x: owner(a), y: name(b) } = p;` → `let Point { x: a, y: b } = p;`
+/// - `let [owner(first), ..] = arr;` → `let [first, ..] = arr;`
+/// - `if let owner(n @ 1..=9) = digit { .. }` → `if let n @ 1..=9 = digit { .. }`
+///
+/// A destructuring pattern matched through coordinates gets its binding mode
+/// spelled out:
+///
+/// - `match coord_shared(o) { Some(name(s)) => .. }` → `match &o { &Some(ref s) => .. }`
+/// - `match coord_exclusive(o) { Some(name(s)) => .. }` → `match &mut o { &mut Some(ref mut s) => .. }`
+///
+/// An `owner(..)` or `rebindable(..)` binding there is rejected, since it only
+/// holds coordinates.
+///
+/// To name part of an owned value without moving it out, use the binding-mode
+/// markers. They are only valid where the binding would otherwise move:
+///
+/// - `name_of(x)` → `ref x` (names part of the value, shared)
+/// - `name_of_exclusive(x)` → `ref mut x` (names part of the value, exclusive)
+///
+/// ```ignore
+/// let (name_of(label), owner(count)) = pair;        // let (ref label, count) = pair;
+/// let Point { x: name_of_exclusive(x), .. } = p;    // let Point { x: ref mut x, .. } = p;
+/// ```
 ///
 /// ## Expression transforms (right side of `=`)
 ///
-/// - `coord_shared(y)` → `&y` (coordinates, many allowed)
-/// - `coord_exclusive(y)` → `&mut y` (coordinates, one allowed)
-/// - `coord_heap(y)` → `Box::new(y)` (coordinates, owning, on heap)
-/// - `mem_copy(y)` → `y` (copy bytes, Copy types)
-/// - `clone_copy(y)` → `y.clone()` (call .clone(), Clone types)
-/// - `take(y)` → `y` (take SPACE, original deleted)
-/// - `take_or_mem_copy(y)` → `y` (compiler decides: mem_copy if Copy, else take)
-/// - `at(r)` → `*r` (get value at coordinates)
+/// Runtime-checked coordinates follow the same rules as the compile-time ones,
+/// verified while the program runs. Raw coordinates carry no proof of validity;
+/// dereferencing them is unchecked.
+#[doc = include_str!("vocabulary.md")]
+///
+/// Every unchecked use must sit inside an `unsafe_proof` form stating why it is
+/// valid. The form becomes an `unsafe` block that keeps the invariant as its doc
+/// attribute, so each asserted proof can be found with grep:
+///
+/// ```ignore
+/// let name(p) = coord_raw(x);
+/// unsafe_proof("p was taken from x, which is alive and not borrowed exclusively") {
+///     let owner(v) = at_unchecked(p);
+/// }
+/// // #[doc = "p was taken from x, ..."] unsafe { let v = *p; }
+/// ```
+///
+/// `#[explicit_fn]` bodies must already parse as Rust, so they use the
+/// attribute form directly: `#[unsafe_proof("..")] unsafe { .. }`.
+///
+/// ## Closure captures
+///
+/// A closure can declare what it captures and how. The form becomes a `move`
+/// closure whose captures are bound first, each like a `let`:
+///
+/// ```ignore
+/// let owner(job) = capture(take(data), coord_shared(label)) |owner(n)| { .. };
+/// // let job = { let data = data; let label = &label; move |n| { .. } };
+/// ```
+///
+/// A capture is `take`, `mem_copy`, `take_or_mem_copy`, `clone_copy`,
+/// `share_owner`, `coord_weak`, `coord_shared` or `coord_exclusive` of one
+/// binding. Any other lowercase name the closure uses but does not declare is
+/// rejected, except `self` inside a method. Ending the list with `; Send`,
+/// `; 'static` or `; Send + 'static` checks the closure can go to another
+/// thread, with errors at the capture responsible:
+///
+/// ```ignore
+/// thread::spawn(capture(take(rx), share_owner(state); Send + 'static) || { .. });
+/// ```
+///
+/// ## Threads
+///
+/// Spawning takes the same capture list, followed by the thread's body:
+///
+/// - `spawn_with(take(x), share_owner(c)) { .. }` → `thread::spawn(move || { .. })`
+/// - `scoped_spawn(coord_shared(y)) { .. }` → `thread::scope(|s| s.spawn(move || { .. }).join())`,
+///   evaluating to what the thread returns
+/// - `scoped_spawn(s, coord_shared(y)) { .. }` → `s.spawn(move || { .. })` inside your own `thread::scope(|s| ..)`
+///
+/// A `spawn_with` thread may outlive the current scope, so it only accepts
+/// captures that own their SPACE; coordinates need `scoped_spawn`. Every
+/// capture is checked where it is written: owned SPACE and exclusive
+/// coordinates must be `Send`, and SPACE named by shared coordinates must be
+/// `Sync`, reported as "IDENTITY cannot cross threads" or "IDENTITY cannot be
+/// shared across threads".
 ///
 /// ## Valid combinations
 ///
-/// | Left | Right | Meaning |
-/// |------|-------|---------|
-/// | `owner(y)` | `take(x)` | tag transfers from x to y |
-/// | `owner(y)` | `mem_copy(x)` | new space, y owns it, x unchanged |
-/// | `owner(y)` | `take_or_mem_copy(x)` | y owns (new or transferred) |
-/// | `owner(y)` | `vec![1,2,3]` | new space created, y owns it |
-/// | `name(r)` | `coord_shared(x)` | r borrows x's space (shared) |
-/// | `name(r)` | `coord_exclusive(x)` | r borrows x's space (exclusive) |
+#[doc = include_str!("combinations.md")]
+///
+/// A right side the notation does not describe, such as `vec![1,2,3]`, is new
+/// SPACE that an `owner(..)` binding owns.
+///
+/// `mem_copy`, `take` and `clone_copy` are checked against the value's type:
+/// `mem_copy(some_string)` fails with "String is not Copy: use take(...) or
+/// clone_copy(...)", and `take` of a `Copy` value fails because nothing is taken.
+///
+/// Pairings that contradict the model, such as `let name(r) = take(x)` or
+/// `let owner(y) = coord_shared(x)`, are rejected with a compile error that
+/// points at both sides.
+///
+/// Rewritten code keeps the spans of the notation it came from, so borrow-checker
+/// errors such as E0382 or E0502 point at the `take(..)` or `coord_shared(..)`
+/// that caused them rather than at the macro call.
 ///
 /// Usage:
 /// ```ignore
@@ -53,25 +155,340 @@ use syn::{
 /// ```
 #[proc_macro]
 pub fn explicit(input: TokenStream) -> TokenStream {
-    let mut block: syn::Block = syn::parse(
-        format!("{{ {} }}", input).parse().unwrap()
-    ).expect("Failed to parse explicit! block");
+    // Parse the statements straight from the input so diagnostics keep their spans
+    let input = captures::attribute_form(proofs::attribute_form(input.into())).into();
+    let mut stmts = syn::parse_macro_input!(input with syn::Block::parse_within);
+
+    let mut captures = captures::Captures::default();
+    let mut proofs = proofs::Proofs::default();
+    let mut transformer = ExplicitTransformer::default();
+    for stmt in &mut stmts {
+        captures.visit_stmt_mut(stmt);
+        proofs.visit_stmt_mut(stmt);
+        transformer.visit_stmt_mut(stmt);
+    }
+
+    let errors = captures.errors.iter().chain(&proofs.errors).chain(&transformer.errors);
+    let errors = errors.map(syn::Error::to_compile_error);
+    quote! { #(#errors)* #(#stmts)* }.into()
+}
+
+/// `explicit!` that narrates the model while the program runs.
+///
+/// Each statement reports what it does to SPACE, and every owner reports the
+/// drop the compiler inserts for it at the end of its scope, or that there is
+/// none for a `Copy` value:
+///
+/// ```text
+/// [space] x owns new SPACE
+/// [space] y takes SPACE from x (x invalid)
+/// [space] r names y's SPACE (shared)
+/// [space] y rebinds: its old SPACE is dropped
+/// [space] y goes out of scope: its SPACE is dropped
+/// [space] x goes out of scope: nothing left to drop
+/// ```
+///
+/// Events go to stderr, or to any `fn(&str)` given as the sink:
+///
+/// ```ignore
+/// fn collect(event: &str) { EVENTS.lock().unwrap().push(event.to_string()) }
+///
+/// explicit_trace! {
+///     sink = collect;
+///     let owner(x) = vec![1, 2, 3];
+///     let owner(y) = take(x);
+/// }
+/// ```
+///
+/// Moves are seen where the notation spells them out (`take`, `drop`,
+/// `take_or_mem_copy`) and where a bare binding is passed by value; whether a
+/// bare binding was copied or moved is decided by its type.
+#[proc_macro]
+pub fn explicit_trace(input: TokenStream) -> TokenStream {
+    let input = captures::attribute_form(proofs::attribute_form(input.into())).into();
+    let mut input = syn::parse_macro_input!(input as trace::Input);
+
+    trace::Tracer::default().instrument(&mut input.stmts);
 
-    let mut transformer = ExplicitTransformer;
-    for stmt in &mut block.stmts {
+    let mut captures = captures::Captures::default();
+    let mut proofs = proofs::Proofs::default();
+    let mut transformer = ExplicitTransformer::default();
+    for stmt in &mut input.stmts {
+        captures.visit_stmt_mut(stmt);
+        proofs.visit_stmt_mut(stmt);
         transformer.visit_stmt_mut(stmt);
     }
 
-    let stmts = &block.stmts;
-    quote! { #(#stmts)* }.into()
+    let errors = captures.errors.iter().chain(&proofs.errors).chain(&transformer.errors);
+    let errors = errors.map(syn::Error::to_compile_error);
+    let prelude = input.prelude();
+    let stmts = &input.stmts;
+    quote! { #(#errors)* #prelude #(#stmts)* }.into()
+}
+
+/// Print what the compiler knows about a type: the auto and marker traits it
+/// implements, and its layout.
+///
+/// Takes a type or an expression. An expression is only borrowed, so probing a
+/// binding leaves it valid:
+///
+/// ```ignore
+/// let counter = Cell::new(0u64);
+/// properties!(counter);
+/// properties!(Arc<Mutex<u64>>);
+/// ```
+///
+/// ```text
+/// properties!(counter): core::cell::Cell<u64>
+///   Copy           no   take(...) transfers it; mem_copy(...) is rejected
+///   Clone          yes  clone_copy(...) makes new SPACE
+///   Send           yes  owned SPACE can cross threads
+///   Sync           no   shared coordinates stay on one thread
+///   ...
+///   size_of        8    bytes
+///   align_of       8    bytes
+///   needs_drop     no   going out of scope frees nothing
+/// ```
+///
+/// Each answer comes from autoref specialization on the concrete type, so
+/// inside a generic function it reflects only the bounds the function states.
+/// `'static` is not probed: lifetimes are erased before trait selection, so a
+/// borrowed type would fail to compile instead of answering no.
+///
+/// A lowercase name is read as a binding and an all-caps name as a constant;
+/// wrap anything else in braces to force an expression: `properties!({ T })`.
+#[proc_macro]
+pub fn properties(input: TokenStream) -> TokenStream {
+    let subject = syn::parse_macro_input!(input as properties::Subject);
+    properties::expand(&subject).into()
+}
+
+/// Derive `space_report(&self) -> String`: where a struct's SPACE lives.
+///
+/// The report lists each field's offset, size and alignment in the order the
+/// compiler laid them out, marks the padding between them, and follows `Box`,
+/// `Vec`, `String`, `Rc` and `Arc` fields to the heap SPACE they own:
+///
+/// ```ignore
+/// struct Inner { value: i32 }
+///
+/// #[derive(SpaceReport)]
+/// struct Outer { inner: Inner, boxed: Box<Inner> }
+///
+/// println!("{}", outer.space_report());
+/// ```
+///
+/// ```text
+/// Outer at 0x7ffd5c3e1a40: 16 bytes, align 8
+/// (fields reordered by the compiler; declared as inner, boxed)
+/// ┌────────────────────────────────────────────┐
+/// │ 0..8    boxed: Box<Inner>  size 8  align 8 │ ---> heap #1
+/// ├────────────────────────────────────────────┤
+/// │ 8..12   inner: Inner       size 4  align 4 │
+/// ├────────────────────────────────────────────┤
+/// │ 12..16  (padding)          size 4          │
+/// └────────────────────────────────────────────┘
+/// (heap #1) at 0x55d0c1a2bb10: 4 bytes
+/// ┌───────┐
+/// │ Inner │ <--- boxed (owner)
+/// └───────┘
+/// ```
+///
+/// Pointers are recognized by name, so `Option<Box<T>>` and other wrappers are
+/// shown as plain fields.
+#[proc_macro_derive(SpaceReport)]
+pub fn space_report(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match report::expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Function-signature notation.
+///
+/// Parameters and the return type are written with the same vocabulary as
+/// `let` bindings:
+///
+/// - `owner(T)` → `T` (caller hands over / receives ownership)
+/// - `name_shared(T)` → `&T` (coordinates, shared)
+/// - `name_exclusive(T)` → `&mut T` (coordinates, exclusive)
+///
+/// A `name_*` output must say which parameters it borrows from with
+/// `from = param` (or `from = a | b`). The macro generates one lifetime tying
+/// the output to those parameters and rejects outputs that borrow from
+/// nothing, from an `owner(..)` parameter, or exclusively from a shared one.
+/// The body is rewritten like an `explicit!` block.
+///
+/// Usage (the "Output to Input Mapping" table from chapter 3):
+/// ```ignore
+/// #[explicit_fn(from = list)]
+/// fn first(list: name_shared([i32])) -> name_shared(i32) { &list[0] }
+///
+/// #[explicit_fn(from = map)]
+/// fn search(map: name_shared(Map), key: name_shared(K)) -> name_shared(V) { .. }
+///
+/// #[explicit_fn(from = a | b)]
+/// fn either(a: name_shared(i32), b: name_shared(i32), flag: owner(bool)) -> name_shared(i32) {
+///     if flag { a } else { b }
+/// }
+///
+/// #[explicit_fn]
+/// fn create() -> owner(Vec<i32>) { vec![1, 2, 3] }
+///
+/// #[explicit_fn]
+/// fn length(list: name_shared([i32])) -> owner(usize) { list.len() }
+/// ```
+#[proc_macro_attribute]
+pub fn explicit_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(attr as signature::Args);
+    let item = signature::angle_bracket_signature(item.into()).into();
+    let mut func = syn::parse_macro_input!(item as syn::ItemFn);
+
+    if let Err(error) = signature::expand(&args, &mut func.sig) {
+        return error.to_compile_error().into();
+    }
+
+    let errors = explicit_body(&func.sig, &mut func.block);
+    let errors = errors.iter().map(syn::Error::to_compile_error);
+    quote! { #(#errors)* #func }.into()
+}
+
+/// Item-level notation: functions, `impl` blocks, traits and modules.
+///
+/// Every function inside is rewritten like an `#[explicit_fn]`, methods and
+/// associated functions included. A method declares its receiver with the
+/// same vocabulary as any other parameter:
+///
+/// - `self: owner(Self)` → `self` (the method consumes the value)
+/// - `self: name_shared(Self)` → `&self`
+/// - `self: name_exclusive(Self)` → `&mut self`
+///
+/// A `name_*` output says where it borrows from with `#[explicit_fn(from =
+/// ..)]` on that function, where `from = self` names the receiver. Structs
+/// inside take `#[explicit_struct]` as usual.
+///
+/// Usage:
+/// ```
+/// # use spelled::{explicit_items, explicit_struct};
+/// explicit_items! {
+///     #[explicit_struct]
+///     struct Counter {
+///         count: owner(u32),
+///     }
+///
+///     impl Counter {
+///         fn new() -> owner(Self) {
+///             Counter { count: 0 }
+///         }
+///
+///         fn bump(self: name_exclusive(Self)) {
+///             self.count += 1;
+///         }
+///
+///         #[explicit_fn(from = self)]
+///         fn count(self: name_shared(Self)) -> name_shared(u32) {
+///             coord_shared(self.count)
+///         }
+///
+///         fn into_count(self: owner(Self)) -> owner(u32) {
+///             let owner(count) = mem_copy(self.count);
+///             count
+///         }
+///     }
+/// }
+/// // impl Counter { fn bump(&mut self) { .. } fn count<'receiver>(&'receiver self) -> &'receiver u32 { .. } .. }
+/// # let mut counter = Counter::new();
+/// # counter.bump();
+/// # assert_eq!(*counter.count(), 1);
+/// # assert_eq!(counter.into_count(), 1);
+/// ```
+#[proc_macro]
+pub fn explicit_items(input: TokenStream) -> TokenStream {
+    let input = captures::attribute_form(proofs::attribute_form(input.into()));
+    let input = signature::angle_bracket_items(input).into();
+    let mut items = syn::parse_macro_input!(input as items::Items).0;
+
+    let mut expander = items::Expander::default();
+    for item in &mut items {
+        expander.visit_item_mut(item);
+    }
+
+    let signature_errors = expander.signature_errors.iter().map(syn::Error::to_compile_error);
+    let errors = expander.errors.iter().map(syn::Error::to_compile_error);
+    if !expander.signature_errors.is_empty() {
+        return quote! { #(#signature_errors)* #(#errors)* }.into();
+    }
+    quote! { #(#errors)* #(#items)* }.into()
+}
+
+/// Rewrite a function body like an `explicit!` block.
+fn explicit_body(sig: &syn::Signature, block: &mut syn::Block) -> Vec<syn::Error> {
+    let mut captures = captures::Captures::in_fn(sig);
+    captures.visit_block_mut(block);
+    let mut proofs = proofs::Proofs::default();
+    proofs.visit_block_mut(block);
+    let mut transformer = ExplicitTransformer::default();
+    transformer.visit_block_mut(block);
+    captures.errors.into_iter().chain(proofs.errors).chain(transformer.errors).collect()
+}
+
+/// Struct-definition notation.
+///
+/// Fields are declared with the same vocabulary as bindings:
+///
+/// - `owner(T)` → `T` (the struct owns this SPACE)
+/// - `name_shared(T)` → `&'field T` (names SPACE owned elsewhere, shared)
+/// - `name_exclusive(T)` → `&'field mut T` (names SPACE owned elsewhere, exclusive)
+/// - `name_shared_raw(T)` / `name_exclusive_raw(T)` → `*const T` / `*mut T`,
+///   tied to `'field` by a generated `_field: PhantomData<&'field T>`
+///
+/// Each field that names outside SPACE gets a lifetime named after it. The
+/// struct's docs and an associated `SPACE_SUMMARY` constant list which fields
+/// own SPACE and which tie the struct to SPACE owned elsewhere.
+///
+/// Usage:
+/// ```ignore
+/// #[explicit_struct]
+/// struct Holder {
+///     label: owner(String),
+///     value: name_shared(i32),
+/// }
+/// // struct Holder<'value> { label: String, value: &'value i32 }
+///
+/// println!("{}", Holder::SPACE_SUMMARY);
+/// ```
+#[proc_macro_attribute]
+pub fn explicit_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if let Some(token) = proc_macro2::TokenStream::from(attr).into_iter().next() {
+        return syn::Error::new(token.span(), "#[explicit_struct] takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let item = types::angle_bracket(item.into()).into();
+    let mut item = syn::parse_macro_input!(item as syn::ItemStruct);
+
+    match structs::expand(&mut item) {
+        Ok(summary) => quote! { #item #summary }.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
 }
 
-struct ExplicitTransformer;
+#[derive(Default)]
+struct ExplicitTransformer {
+    errors: Vec<syn::Error>,
+}
 
 impl VisitMut for ExplicitTransformer {
     fn visit_local_mut(&mut self, local: &mut Local) {
+        // Check the combination before either side is rewritten
+        if let Some(init) = &local.init {
+            combinations::check(&local.pat, &init.expr, &mut self.errors);
+        }
+
         // First transform the pattern
-        transform_pat(&mut local.pat);
+        let init = local.init.as_ref().map(|init| &*init.expr);
+        transform_binding(&mut local.pat, init, &mut self.errors);
 
         // Then transform the init expression
         if let Some(init) = &mut local.init {
@@ -81,60 +498,325 @@ impl VisitMut for ExplicitTransformer {
         visit_mut::visit_local_mut(self, local);
     }
 
+    fn visit_expr_match_mut(&mut self, expr_match: &mut syn::ExprMatch) {
+        // match arms see the scrutinee before it is rewritten
+        for arm in &mut expr_match.arms {
+            combinations::check(&arm.pat, &expr_match.expr, &mut self.errors);
+            transform_binding(&mut arm.pat, Some(&expr_match.expr), &mut self.errors);
+        }
+        visit_mut::visit_expr_match_mut(self, expr_match);
+    }
+
+    fn visit_expr_let_mut(&mut self, expr_let: &mut syn::ExprLet) {
+        // if let / while let
+        combinations::check(&expr_let.pat, &expr_let.expr, &mut self.errors);
+        transform_binding(&mut expr_let.pat, Some(&expr_let.expr), &mut self.errors);
+        visit_mut::visit_expr_let_mut(self, expr_let);
+    }
+
+    fn visit_expr_for_loop_mut(&mut self, for_loop: &mut syn::ExprForLoop) {
+        // Items are whatever the iterator yields, so nothing is known about them
+        transform_pat(&mut for_loop.pat, Matched::ByValue, &mut self.errors);
+        visit_mut::visit_expr_for_loop_mut(self, for_loop);
+    }
+
+    fn visit_expr_closure_mut(&mut self, closure: &mut syn::ExprClosure) {
+        for input in &mut closure.inputs {
+            transform_pat(input, Matched::ByValue, &mut self.errors);
+        }
+        visit_mut::visit_expr_closure_mut(self, closure);
+    }
+
+    fn visit_fn_arg_mut(&mut self, arg: &mut syn::FnArg) {
+        if let syn::FnArg::Typed(typed) = arg {
+            transform_pat(&mut typed.pat, Matched::ByValue, &mut self.errors);
+        }
+        visit_mut::visit_fn_arg_mut(self, arg);
+    }
+
     fn visit_expr_mut(&mut self, expr: &mut Expr) {
         transform_expr(expr);
         visit_mut::visit_expr_mut(self, expr);
     }
 }
 
-fn transform_pat(pat: &mut Pat) {
+/// How the value a pattern matches is reached.
+///
+/// Matching through coordinates switches Rust's default binding mode, so every
+/// binding inside a destructuring pattern becomes `ref` or `ref mut` on its own.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Matched {
+    ByValue,
+    Shared,
+    Exclusive,
+}
+
+impl Matched {
+    fn of(expr: &Expr) -> Matched {
+        match expr {
+            Expr::Call(call) => {
+                let op = get_ident_name(&call.func).and_then(|name| vocabulary::lookup(&name));
+                match op.and_then(|op| op.rewrite.coordinates()) {
+                    Some(true) => Matched::Exclusive,
+                    Some(false) => Matched::Shared,
+                    None => Matched::ByValue,
+                }
+            }
+            Expr::Reference(reference) if reference.mutability.is_some() => Matched::Exclusive,
+            Expr::Reference(_) => Matched::Shared,
+            Expr::Paren(paren) => Matched::of(&paren.expr),
+            _ => Matched::ByValue,
+        }
+    }
+}
+
+/// Transform a pattern against the expression it matches, pairwise for tuples.
+fn transform_binding(pat: &mut Pat, expr: Option<&Expr>, errors: &mut Vec<syn::Error>) {
+    match (pat, expr) {
+        (Pat::Tuple(pats), Some(Expr::Tuple(exprs))) if pats.elems.len() == exprs.elems.len() => {
+            for (pat, expr) in pats.elems.iter_mut().zip(&exprs.elems) {
+                transform_binding(pat, Some(expr), errors);
+            }
+        }
+        (pat, expr) => transform_pat(pat, expr.map_or(Matched::ByValue, Matched::of), errors),
+    }
+}
+
+fn transform_pat(pat: &mut Pat, matched: Matched, errors: &mut Vec<syn::Error>) {
     match pat {
         // owner(x) or name(x) → x
         // owner(rebindable(x)) or name(rebindable(x)) → mut x
-        Pat::TupleStruct(ts) if is_path_ident(&ts.path, "owner") || is_path_ident(&ts.path, "name") => {
-            if let Some(first) = ts.elems.first() {
-                match first {
-                    // owner(rebindable(x)) or name(rebindable(x)) → mut x
-                    Pat::TupleStruct(inner) if is_path_ident(&inner.path, "rebindable") => {
-                        if let Some(inner_first) = inner.elems.first() {
-                            if let Pat::Ident(ident) = inner_first {
-                                *pat = Pat::Ident(PatIdent {
-                                    attrs: vec![],
-                                    by_ref: None,
-                                    mutability: Some(Token![mut](ts.path.span())),
-                                    ident: ident.ident.clone(),
-                                    subpat: None,
-                                });
-                            }
-                        }
-                    }
-                    // owner(x) or name(x) → x
-                    Pat::Ident(ident) => {
-                        *pat = Pat::Ident(PatIdent {
-                            attrs: vec![],
-                            by_ref: None,
-                            mutability: None,
-                            ident: ident.ident.clone(),
-                            subpat: None,
-                        });
-                    }
-                    _ => {}
+        // owner(n @ 1..=9) → n @ 1..=9
+        // name_of(x) → ref x, name_of_exclusive(x) → ref mut x
+        Pat::TupleStruct(ts) if is_name_of(ts) => transform_name_of(pat, matched, errors),
+        Pat::TupleStruct(ts) if is_marker(ts) => {
+            if let Some((mut ident, rebindable)) = marker_binding(ts) {
+                ident.mutability = rebindable.then(|| Token![mut](ts.path.span()));
+                if let Some((_, subpat)) = &mut ident.subpat {
+                    transform_pat(subpat, matched, errors);
                 }
+                *pat = Pat::Ident(ident);
             }
         }
+        // whole @ Some(owner(s)) → whole @ Some(s)
+        Pat::Ident(ident) => {
+            if let Some((_, subpat)) = &mut ident.subpat {
+                transform_pat(subpat, matched, errors);
+            }
+        }
+        // Ok(owner(x)) | Err(owner(x)) → Ok(x) | Err(x)
+        Pat::Or(or) => {
+            for case in &mut or.cases {
+                transform_pat(case, matched, errors);
+            }
+        }
+        Pat::Paren(paren) => {
+            transform_pat(&mut paren.pat, matched, errors);
+        }
+        // An explicit `&` pattern already dereferences, so what is under it is matched by value
+        // &(owner(a), name(b)) → &(a, b)
+        Pat::Reference(reference) => {
+            transform_pat(&mut reference.pat, Matched::ByValue, errors);
+        }
+        // owner(x): T → x: T
+        Pat::Type(typed) => {
+            transform_pat(&mut typed.pat, matched, errors);
+        }
         // (name(a), name(b)) → (a, b)
+        // Some(owner(s)) → Some(s)
+        // Point { x: owner(a), y: name(b) } → Point { x: a, y: b }
+        // [owner(first), ..] → [first, ..]
+        Pat::Tuple(_) | Pat::TupleStruct(_) | Pat::Struct(_) | Pat::Slice(_) => {
+            transform_nested(pat, matched, errors);
+            // Spell out the binding mode Rust would otherwise pick silently:
+            // Some(name(s)) matched through coord_shared(..) → &Some(ref s)
+            if matched != Matched::ByValue {
+                let span = pat.span();
+                let inner = std::mem::replace(pat, Pat::Verbatim(proc_macro2::TokenStream::new()));
+                *pat = Pat::Reference(syn::PatReference {
+                    attrs: vec![],
+                    and_token: Token![&](span),
+                    mutability: (matched == Matched::Exclusive).then(|| Token![mut](span)),
+                    pat: Box::new(inner),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Transform the bindings inside a destructuring pattern.
+///
+/// Matched through coordinates, each binding is `ref`/`ref mut`: it names SPACE
+/// owned elsewhere, so it cannot be an `owner` and cannot be rebound.
+fn transform_nested(pat: &mut Pat, matched: Matched, errors: &mut Vec<syn::Error>) {
+    match pat {
+        Pat::TupleStruct(ts) if is_name_of(ts) => transform_name_of(pat, matched, errors),
+        Pat::TupleStruct(ts) if is_marker(ts) && matched != Matched::ByValue => {
+            let via = if matched == Matched::Shared { "coord_shared" } else { "coord_exclusive" };
+            if !is_path_ident(&ts.path, "name") {
+                let marker = ts.path.to_token_stream();
+                errors.push(syn::Error::new_spanned(
+                    &*ts,
+                    format!(
+                        "`{marker}(..)` is matched through {via}(..): the binding names SPACE owned elsewhere, \
+                         write `name(..)`"
+                    ),
+                ));
+            }
+            let Some((mut ident, rebindable)) = marker_binding(ts) else {
+                return;
+            };
+            if rebindable {
+                errors.push(syn::Error::new_spanned(
+                    &*ts,
+                    format!("a binding matched through {via}(..) holds coordinates and cannot be `rebindable(..)`"),
+                ));
+            }
+            let span = ts.path.span();
+            ident.by_ref = Some(Token![ref](span));
+            ident.mutability = (matched == Matched::Exclusive).then(|| Token![mut](span));
+            if let Some((_, subpat)) = &mut ident.subpat {
+                transform_nested(subpat, matched, errors);
+            }
+            *pat = Pat::Ident(ident);
+        }
+        Pat::TupleStruct(ts) if is_marker(ts) => transform_pat(pat, matched, errors),
+        Pat::Ident(ident) => {
+            // Plain bindings keep the mode Rust would have given them: Some(s) → &Some(ref s).
+            // Capitalized names are unit variants or constants, Some(None), not bindings
+            let binding = !ident.ident.to_string().starts_with(char::is_uppercase);
+            if matched != Matched::ByValue && binding && ident.by_ref.is_none() && ident.mutability.is_none() {
+                let span = ident.ident.span();
+                ident.by_ref = Some(Token![ref](span));
+                ident.mutability = (matched == Matched::Exclusive).then(|| Token![mut](span));
+            }
+            if let Some((_, subpat)) = &mut ident.subpat {
+                transform_nested(subpat, matched, errors);
+            }
+        }
         Pat::Tuple(tuple) => {
             for elem in &mut tuple.elems {
-                transform_pat(elem);
+                transform_nested(elem, matched, errors);
+            }
+        }
+        Pat::TupleStruct(ts) => {
+            for elem in &mut ts.elems {
+                transform_nested(elem, matched, errors);
+            }
+        }
+        // Shorthand fields stay shorthand: Point { x } → &Point { ref x }
+        Pat::Struct(st) => {
+            for field in &mut st.fields {
+                transform_nested(&mut field.pat, matched, errors);
+                // Point { x: owner(x) } → Point { x }, not Point { x: x }
+                if let (syn::Member::Named(member), Pat::Ident(ident)) = (&field.member, &*field.pat) {
+                    if *member == ident.ident && ident.subpat.is_none() {
+                        field.colon_token = None;
+                    }
+                }
+            }
+        }
+        Pat::Slice(slice) => {
+            for elem in &mut slice.elems {
+                transform_nested(elem, matched, errors);
+            }
+        }
+        Pat::Reference(reference) => {
+            transform_nested(&mut reference.pat, Matched::ByValue, errors);
+        }
+        Pat::Or(or) => {
+            for case in &mut or.cases {
+                transform_nested(case, matched, errors);
             }
         }
         Pat::Paren(paren) => {
-            transform_pat(&mut paren.pat);
+            transform_nested(&mut paren.pat, matched, errors);
         }
         _ => {}
     }
 }
 
+/// `name_of(x)` → `ref x`, `name_of_exclusive(x)` → `ref mut x`.
+///
+/// Names part of an owned value instead of moving it out, so it only makes
+/// sense where the binding would otherwise move. Matched through coordinates,
+/// Rust already binds by reference and the marker is rejected.
+fn transform_name_of(pat: &mut Pat, matched: Matched, errors: &mut Vec<syn::Error>) {
+    let Pat::TupleStruct(ts) = pat else {
+        return;
+    };
+    let marker = if is_path_ident(&ts.path, "name_of") { "name_of" } else { "name_of_exclusive" };
+    if matched != Matched::ByValue {
+        let via = if matched == Matched::Shared { "coord_shared" } else { "coord_exclusive" };
+        errors.push(syn::Error::new_spanned(
+            &*ts,
+            format!(
+                "`{marker}(..)` is matched through {via}(..), which already binds by reference: \
+                 nothing would be moved here, write `name(..)`"
+            ),
+        ));
+    }
+    let (inner, rebindable) = match ts.elems.first() {
+        Some(Pat::TupleStruct(inner)) if is_path_ident(&inner.path, "rebindable") => (inner.elems.first(), true),
+        inner => (inner, false),
+    };
+    if rebindable {
+        errors.push(syn::Error::new_spanned(
+            &*ts,
+            format!("`{marker}(..)` binds coordinates into the value and cannot be `rebindable(..)`"),
+        ));
+    }
+    let Some(Pat::Ident(ident)) = inner else {
+        errors.push(syn::Error::new_spanned(&*ts, format!("expected `{marker}(<binding>)`")));
+        return;
+    };
+    let span = ts.path.span();
+    let mut ident = PatIdent {
+        attrs: vec![],
+        by_ref: Some(Token![ref](span)),
+        mutability: (marker == "name_of_exclusive").then(|| Token![mut](span)),
+        ..ident.clone()
+    };
+    if let Some((_, subpat)) = &mut ident.subpat {
+        transform_nested(subpat, matched, errors);
+    }
+    *pat = Pat::Ident(ident);
+}
+
+fn is_name_of(ts: &syn::PatTupleStruct) -> bool {
+    is_path_ident(&ts.path, "name_of") || is_path_ident(&ts.path, "name_of_exclusive")
+}
+
+fn is_marker(ts: &syn::PatTupleStruct) -> bool {
+    marker_of(ts).is_some()
+}
+
+/// The marker a pattern is written with, `owner(..)` → `Marker::Owner`.
+fn marker_of(ts: &syn::PatTupleStruct) -> Option<vocabulary::Marker> {
+    vocabulary::Marker::of(&ts.path.get_ident()?.to_string())
+}
+
+/// The binding inside `owner(x)` / `name(rebindable(x))`, and whether it is rebindable.
+fn marker_binding(ts: &syn::PatTupleStruct) -> Option<(PatIdent, bool)> {
+    let (inner, rebindable) = match ts.elems.first()? {
+        Pat::TupleStruct(inner) if is_path_ident(&inner.path, "rebindable") => (inner.elems.first()?, true),
+        inner => (inner, false),
+    };
+    match inner {
+        Pat::Ident(ident) => Some((
+            PatIdent {
+                attrs: vec![],
+                by_ref: None,
+                mutability: None,
+                ..ident.clone()
+            },
+            rebindable,
+        )),
+        _ => None,
+    }
+}
+
 fn is_path_ident(path: &syn::Path, name: &str) -> bool {
     path.get_ident().map(|i| i == name).unwrap_or(false)
 }
@@ -142,64 +824,50 @@ fn is_path_ident(path: &syn::Path, name: &str) -> bool {
 fn transform_expr(expr: &mut Expr) {
     match expr {
         Expr::Call(call) => {
-            let func_name = get_ident_name(&call.func);
-
-            match func_name.as_deref() {
-                Some("coord_shared") => {
-                    // coord_shared(y) → &y
-                    if let Some(arg) = call.args.first() {
-                        let mut arg = arg.clone();
-                        transform_expr(&mut arg);
-                        *expr = syn::parse_quote! { &#arg };
-                    }
-                }
-                Some("coord_exclusive") => {
-                    // coord_exclusive(y) → &mut y
-                    if let Some(arg) = call.args.first() {
-                        let mut arg = arg.clone();
-                        transform_expr(&mut arg);
-                        *expr = syn::parse_quote! { &mut #arg };
-                    }
+            let op = get_ident_name(&call.func).and_then(|name| vocabulary::lookup(&name));
+            let Some(op) = op.filter(|op| call.args.len() >= op.rewrite.arity()) else {
+                // Transform arguments of other calls
+                for arg in &mut call.args {
+                    transform_expr(arg);
                 }
-                Some("coord_heap") => {
-                    // coord_heap(y) → Box::new(y)
-                    if let Some(arg) = call.args.first() {
-                        let mut arg = arg.clone();
-                        transform_expr(&mut arg);
-                        *expr = syn::parse_quote! { Box::new(#arg) };
-                    }
-                }
-                Some("mem_copy") | Some("take") | Some("take_or_mem_copy") => {
-                    // mem_copy(y), take(y), take_or_mem_copy(y) → y
-                    if let Some(arg) = call.args.first() {
-                        let mut arg = arg.clone();
-                        transform_expr(&mut arg);
-                        *expr = arg;
-                    }
+                return;
+            };
+            // Synthesized tokens carry the span of the notation call they replace,
+            // so borrow-checker errors point at the line that wrote it.
+            let span = call.func.span();
+            let mut args: Vec<Expr> = call.args.iter().cloned().collect();
+            for arg in &mut args {
+                transform_expr(arg);
+            }
+            let arg = &args[0];
+            *expr = match op.rewrite {
+                Rewrite::Prefix(prefix) => {
+                    let prefix = respan(prefix, span);
+                    syn::parse_quote_spanned! {span=> #prefix #arg }
                 }
-                Some("clone_copy") => {
-                    // clone_copy(y) → y.clone()
-                    if let Some(arg) = call.args.first() {
-                        let mut arg = arg.clone();
-                        transform_expr(&mut arg);
-                        *expr = syn::parse_quote! { #arg.clone() };
-                    }
+                Rewrite::Call(func) => {
+                    let func = respan(func, span);
+                    syn::parse_quote_spanned! {span=> #func(#arg) }
                 }
-                Some("at") => {
-                    // at(r) → *r
-                    if let Some(arg) = call.args.first() {
-                        let mut arg = arg.clone();
-                        transform_expr(&mut arg);
-                        *expr = syn::parse_quote! { *#arg };
-                    }
+                Rewrite::Suffix(suffix) => {
+                    let suffix = respan(suffix, span);
+                    syn::parse_quote_spanned! {span=> #arg #suffix }
                 }
-                _ => {
-                    // Transform arguments of other calls
-                    for arg in &mut call.args {
-                        transform_expr(arg);
-                    }
+                Rewrite::Method(method) => {
+                    let method = syn::Ident::new(method, span);
+                    let value = &args[1];
+                    syn::parse_quote_spanned! {span=> #arg.#method(#value) }
                 }
-            }
+                Rewrite::Same => arg.clone(),
+                Rewrite::Generated(generated) => match generated {
+                    Generated::MemCopy => claims::mem_copy(arg, span),
+                    Generated::Take => claims::take(arg, span),
+                    Generated::CloneCopy => claims::clone_copy(arg, span),
+                    Generated::ShareOwner => counted::share_owner(arg, span),
+                    Generated::CoordWeak => counted::coord_weak(arg, span),
+                    Generated::TryAt => counted::try_at(arg, span),
+                },
+            };
         }
         Expr::Tuple(tuple) => {
             for elem in &mut tuple.elems {
@@ -219,6 +887,23 @@ fn transform_expr(expr: &mut Expr) {
     }
 }
 
+/// Parse a fixed piece of a rewrite, giving every token the notation's span.
+fn respan(text: &str, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    let tokens: proc_macro2::TokenStream = text.parse().expect("vocabulary rewrites are valid tokens");
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let proc_macro2::TokenTree::Group(group) = &token {
+                let mut inner = proc_macro2::Group::new(group.delimiter(), respan(&group.stream().to_string(), span));
+                inner.set_span(span);
+                token = proc_macro2::TokenTree::Group(inner);
+            }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
 fn get_ident_name(expr: &Expr) -> Option<String> {
     if let Expr::Path(path) = expr {
         path.path.get_ident().map(|i| i.to_string())
@@ -0,0 +1,152 @@
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, TokenStream, TokenTree};
+use quote::quote_spanned;
+use syn::{
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Expr, ExprUnsafe, LitStr, Stmt,
+};
+
+use crate::get_ident_name;
+
+/// Operations that dereference raw coordinates, which the compiler cannot check.
+const UNCHECKED: &[&str] = &["at_unchecked", "coord_from_raw"];
+
+/// Rewrite `unsafe_proof("..") { .. }` to `#[unsafe_proof("..")] unsafe { .. }`.
+///
+/// A call followed by a block is not an expression syn can parse, so the form
+/// is turned into an attributed `unsafe` block before parsing.
+pub(crate) fn attribute_form(tokens: TokenStream) -> TokenStream {
+    let mut rewritten = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "unsafe_proof" => {
+                let invariant = tokens.next_if(|next| {
+                    matches!(next, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)
+                });
+                let body = invariant.is_some().then(|| {
+                    tokens.next_if(|next| matches!(next, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace))
+                });
+                match (invariant, body.flatten()) {
+                    (Some(TokenTree::Group(invariant)), Some(TokenTree::Group(body))) => {
+                        let span = ident.span();
+                        let mut pound = Punct::new('#', Spacing::Alone);
+                        pound.set_span(span);
+                        let attr = Group::new(
+                            Delimiter::Bracket,
+                            [TokenTree::Ident(ident), TokenTree::Group(invariant)].into_iter().collect(),
+                        );
+                        let mut body_group = Group::new(Delimiter::Brace, attribute_form(body.stream()));
+                        body_group.set_span(body.span());
+                        rewritten.push(TokenTree::Punct(pound));
+                        rewritten.push(TokenTree::Group(attr));
+                        rewritten.push(TokenTree::Ident(Ident::new("unsafe", span)));
+                        rewritten.push(TokenTree::Group(body_group));
+                    }
+                    (invariant, _) => {
+                        rewritten.push(TokenTree::Ident(ident));
+                        rewritten.extend(invariant.map(|invariant| match invariant {
+                            TokenTree::Group(group) => {
+                                let mut inner = Group::new(group.delimiter(), attribute_form(group.stream()));
+                                inner.set_span(group.span());
+                                TokenTree::Group(inner)
+                            }
+                            other => other,
+                        }));
+                    }
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut inner = Group::new(group.delimiter(), attribute_form(group.stream()));
+                inner.set_span(group.span());
+                rewritten.push(TokenTree::Group(inner));
+            }
+            other => rewritten.push(other),
+        }
+    }
+    rewritten.into_iter().collect()
+}
+
+/// Expands proof blocks and rejects unchecked operations outside of one.
+///
+/// `#[unsafe_proof("..")] unsafe { .. }` becomes an `unsafe` block carrying the
+/// invariant as `#[doc = ".."]`, so every asserted proof stays greppable in the
+/// expansion as well as in the source.
+#[derive(Default)]
+pub(crate) struct Proofs {
+    depth: usize,
+    pub(crate) errors: Vec<syn::Error>,
+}
+
+impl Proofs {
+    /// Turn the `unsafe_proof` attribute into a doc attribute and check the block.
+    fn prove(&mut self, block: &mut ExprUnsafe, index: usize) {
+        let attr = block.attrs.remove(index);
+        let invariant = match attr.parse_args::<LitStr>() {
+            Ok(invariant) if !invariant.value().trim().is_empty() => invariant,
+            Ok(invariant) => {
+                self.errors.push(syn::Error::new(invariant.span(), "state the invariant this block relies on"));
+                invariant
+            }
+            Err(_) => {
+                self.errors.push(syn::Error::new_spanned(
+                    &attr.meta,
+                    "expected `unsafe_proof(\"<why it is valid>\") { .. }`",
+                ));
+                LitStr::new("", attr.span())
+            }
+        };
+        let span = block.unsafe_token.span;
+        block.attrs.insert(index, syn::parse_quote_spanned! {span=> #[doc = #invariant] });
+        block.attrs.insert(index, syn::parse_quote_spanned! {span=> #[allow(unused_doc_comments)] });
+
+        self.depth += 1;
+        visit_mut::visit_expr_unsafe_mut(self, block);
+        self.depth -= 1;
+    }
+}
+
+impl VisitMut for Proofs {
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        // Attributes are allowed on statements, so the proof stays where it is
+        if let Stmt::Expr(Expr::Unsafe(block), _) = stmt {
+            if let Some(index) = proof_attr(block) {
+                return self.prove(block, index);
+            }
+        }
+        visit_mut::visit_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Call(call) = expr {
+            if let Some(op) = get_ident_name(&call.func).filter(|op| UNCHECKED.contains(&op.as_str())) {
+                if self.depth == 0 {
+                    self.errors.push(syn::Error::new_spanned(
+                        &*call,
+                        format!(
+                            "`{op}(..)` dereferences raw coordinates the compiler cannot check: \
+                             put it inside `unsafe_proof(\"<why it is valid>\") {{ .. }}`"
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let Expr::Unsafe(block) = expr else {
+            return visit_mut::visit_expr_mut(self, expr);
+        };
+        let Some(index) = proof_attr(block) else {
+            return visit_mut::visit_expr_mut(self, expr);
+        };
+        self.prove(block, index);
+
+        // Elsewhere attributes are not allowed, so the proof becomes a statement of its own
+        let span = block.unsafe_token.span;
+        *expr = syn::parse2(quote_spanned! {span=> { #block } })
+            .expect("unsafe_proof expansion is a block expression");
+    }
+}
+
+fn proof_attr(block: &ExprUnsafe) -> Option<usize> {
+    block.attrs.iter().position(|attr| attr.path().is_ident("unsafe_proof"))
+}
@@ -0,0 +1,231 @@
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Type};
+
+// Each property is probed with autoref specialization: `Has` is implemented
+// for `PropertiesProbe<T>` only when the bound holds, `Lacks` for every
+// `&PropertiesProbe<T>`. Method lookup on `&PropertiesProbe<T>` tries `Has` first and falls back to `Lacks`,
+// so the answer is a plain `bool` instead of a compile error.
+
+/// What `properties!` was given.
+pub(crate) enum Subject {
+    Type(Type),
+    Expr(Expr),
+}
+
+impl Parse for Subject {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        if let Ok(ty) = fork.parse::<Type>() {
+            if fork.is_empty() && names_type(&ty) {
+                input.parse::<Type>()?;
+                return Ok(Subject::Type(ty));
+            }
+        }
+        Ok(Subject::Expr(input.parse()?))
+    }
+}
+
+/// Whether something that parses as a type reads as one. A lowercase path like
+/// `counter` is a binding, unless it is a primitive type; an all-caps path like
+/// `CONFIG` is a constant or static.
+fn names_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let Some(last) = path.path.segments.last() else { return false };
+            let name = last.ident.to_string();
+            if PRIMITIVES.contains(&name.as_str()) {
+                return true;
+            }
+            let upper = name.starts_with(|c: char| c.is_ascii_uppercase());
+            let constant = name.len() > 1 && !name.chars().any(|c| c.is_ascii_lowercase());
+            upper && !constant
+        }
+        Type::Reference(reference) => names_type(&reference.elem),
+        Type::Paren(paren) => names_type(&paren.elem),
+        Type::Slice(slice) => names_type(&slice.elem),
+        Type::Array(array) => names_type(&array.elem),
+        Type::Tuple(tuple) => tuple.elems.iter().all(names_type),
+        // `vec![..]` and friends build values
+        Type::Macro(_) => false,
+        _ => true,
+    }
+}
+
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+/// A probed trait, with what each answer means in the notation.
+struct Property {
+    name: &'static str,
+    bound: TokenStream,
+    yes: &'static str,
+    no: &'static str,
+}
+
+fn properties() -> Vec<Property> {
+    vec![
+        Property {
+            name: "Copy",
+            bound: quote!(::std::marker::Copy),
+            yes: "mem_copy(...) duplicates the bytes",
+            no: "take(...) transfers it; mem_copy(...) is rejected",
+        },
+        Property {
+            name: "Clone",
+            bound: quote!(::std::clone::Clone),
+            yes: "clone_copy(...) makes new SPACE",
+            no: "clone_copy(...) is rejected",
+        },
+        Property {
+            name: "Send",
+            bound: quote!(::std::marker::Send),
+            yes: "owned SPACE can cross threads",
+            no: "stays on the thread that created it",
+        },
+        Property {
+            name: "Sync",
+            bound: quote!(::std::marker::Sync),
+            yes: "coord_shared(...) can cross threads",
+            no: "shared coordinates stay on one thread",
+        },
+        Property {
+            name: "Unpin",
+            bound: quote!(::std::marker::Unpin),
+            yes: "can be moved even after pinning",
+            no: "stays at its coordinates once pinned",
+        },
+        Property {
+            name: "UnwindSafe",
+            bound: quote!(::std::panic::UnwindSafe),
+            yes: "catch_unwind may take it",
+            no: "may be left half-updated by a panic",
+        },
+        Property {
+            name: "RefUnwindSafe",
+            bound: quote!(::std::panic::RefUnwindSafe),
+            yes: "catch_unwind may take coord_shared(...) of it",
+            no: "shared coordinates may see a half-updated value",
+        },
+    ]
+}
+
+/// Expand to a block that prints the property table at runtime.
+pub(crate) fn expand(subject: &Subject) -> TokenStream {
+    let span = Span::mixed_site();
+    let value = syn::Ident::new("value", span);
+    let (evaluate, probe, shown) = match subject {
+        Subject::Type(ty) => (
+            quote!(),
+            quote!(PropertiesProbe::<#ty>(::std::marker::PhantomData)),
+            shown(quote!(#ty)),
+        ),
+        // Borrowed, so probing a binding does not take it
+        Subject::Expr(expr) => (
+            quote!(let #value = &(#expr);),
+            quote!(properties_probe(#value)),
+            shown(quote!(#expr)),
+        ),
+    };
+
+    let rows = properties().into_iter().map(|Property { name, bound, yes, no }| {
+        quote! {{
+            trait Has { fn has(&self) -> bool { true } }
+            impl<T: ?Sized + #bound> Has for PropertiesProbe<T> {}
+            trait Lacks { fn has(&self) -> bool { false } }
+            impl<T: ?Sized> Lacks for &PropertiesProbe<T> {}
+            let has = (&probe).has();
+            rows.push((#name, if has { "yes" } else { "no" }, if has { #yes } else { #no }));
+        }}
+    });
+
+    quote! {{
+        #evaluate
+        {
+            struct PropertiesProbe<T: ?Sized>(::std::marker::PhantomData<T>);
+            #[allow(dead_code)]
+            fn properties_probe<T: ?Sized>(_: &T) -> PropertiesProbe<T> {
+                PropertiesProbe(::std::marker::PhantomData)
+            }
+            impl<T: ?Sized> PropertiesProbe<T> {
+                fn type_name(&self) -> &'static str { ::std::any::type_name::<T>() }
+                fn needs_drop(&self) -> bool { ::std::mem::needs_drop::<T>() }
+            }
+
+            // Size and alignment only exist for Sized types
+            trait Layout { fn layout(&self) -> ::std::option::Option<(usize, usize)>; }
+            impl<T> Layout for PropertiesProbe<T> {
+                fn layout(&self) -> ::std::option::Option<(usize, usize)> {
+                    ::std::option::Option::Some((::std::mem::size_of::<T>(), ::std::mem::align_of::<T>()))
+                }
+            }
+            trait Unsized { fn layout(&self) -> ::std::option::Option<(usize, usize)> { ::std::option::Option::None } }
+            impl<T: ?Sized> Unsized for &PropertiesProbe<T> {}
+
+            let probe = #probe;
+            let mut rows: ::std::vec::Vec<(&str, &str, &str)> = ::std::vec::Vec::new();
+            #(#rows)*
+            let layout = (&probe).layout();
+            rows.push(match layout {
+                ::std::option::Option::Some(_) => ("Sized", "yes", "can live in a binding directly"),
+                ::std::option::Option::None => ("Sized", "no", "only reachable through coordinates"),
+            });
+            ::std::println!("properties!({}): {}", #shown, probe.type_name());
+            for (name, answer, meaning) in &rows {
+                ::std::println!("  {name:<14} {answer:<4} {meaning}");
+            }
+            if let ::std::option::Option::Some((size, align)) = layout {
+                ::std::println!("  {:<14} {size:<4} bytes", "size_of");
+                ::std::println!("  {:<14} {align:<4} bytes", "align_of");
+            }
+            let drops = probe.needs_drop();
+            ::std::println!("  {:<14} {:<4} {}", "needs_drop",
+                if drops { "yes" } else { "no" },
+                if drops { "going out of scope runs drop code" } else { "going out of scope frees nothing" });
+        }
+    }}
+}
+
+/// The subject as written, for the table's heading. `stringify!` would space
+/// every token apart: `Arc < Mutex < u64 > >`.
+pub(crate) fn shown(tokens: TokenStream) -> String {
+    let mut text = String::new();
+    let mut glued = true;
+    for token in tokens {
+        let tight = match &token {
+            TokenTree::Punct(punct) => matches!(punct.as_char(), ',' | ';' | '.' | ':' | '<' | '>' | '?' | '!'),
+            // A call, index or generic argument list hugs what precedes it
+            TokenTree::Group(group) => {
+                group.delimiter() != Delimiter::Brace && text.ends_with(|c: char| c.is_alphanumeric() || "_!>)".contains(c))
+            }
+            _ => false,
+        };
+        if !glued && !tight {
+            text.push(' ');
+        }
+        glued = match &token {
+            TokenTree::Punct(punct) => {
+                punct.spacing() == Spacing::Joint || matches!(punct.as_char(), '&' | '*' | '<' | '.' | ':' | '!' | '\'')
+            }
+            _ => false,
+        };
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                text.push_str(open);
+                text.push_str(&shown(group.stream()));
+                text.push_str(close);
+            }
+            token => text.push_str(&token.to_string()),
+        }
+    }
+    text
+}
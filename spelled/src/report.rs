@@ -0,0 +1,231 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, Index, Member, Type};
+
+// The report is built at runtime from `offset_of!` and `size_of_val`, so it
+// shows the layout the compiler actually chose, reordering and padding
+// included. Heap SPACE is followed only for the owning pointers the course
+// uses, recognized by name.
+
+/// Owning pointers whose allocation is drawn as heap SPACE.
+#[derive(Clone, Copy)]
+enum Heap {
+    Box,
+    Vec,
+    String,
+    Rc,
+    Arc,
+}
+
+fn heap_kind(ty: &Type) -> Option<Heap> {
+    let Type::Path(path) = ty else { return None };
+    let last = path.path.segments.last()?;
+    Some(match last.ident.to_string().as_str() {
+        "Box" => Heap::Box,
+        "Vec" => Heap::Vec,
+        "String" => Heap::String,
+        "Rc" => Heap::Rc,
+        "Arc" => Heap::Arc,
+        _ => return None,
+    })
+}
+
+/// `#[derive(SpaceReport)]` → an inherent `space_report(&self) -> String`.
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "SpaceReport draws struct layouts: enums and unions have no fixed field offsets",
+        ));
+    };
+    let members: Vec<(Member, &Type)> = match &data.fields {
+        Fields::Named(fields) => {
+            fields.named.iter().map(|field| (Member::Named(field.ident.clone().unwrap()), &field.ty)).collect()
+        }
+        Fields::Unnamed(fields) => {
+            fields.unnamed.iter().enumerate().map(|(i, field)| (Member::Unnamed(Index::from(i)), &field.ty)).collect()
+        }
+        Fields::Unit => Vec::new(),
+    };
+
+    let fields = members.iter().map(|(member, ty)| {
+        let name = match member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        };
+        let shown = crate::properties::shown(quote!(#ty));
+        let heap = match heap_kind(ty) {
+            Some(Heap::Box) => quote!(::std::option::Option::Some(heap_box(&self.#member))),
+            Some(Heap::Vec) => quote!(::std::option::Option::Some(heap_vec(&self.#member))),
+            Some(Heap::String) => quote!(::std::option::Option::Some(heap_string(&self.#member))),
+            Some(Heap::Rc) => quote!(::std::option::Option::Some(heap_rc(&self.#member))),
+            Some(Heap::Arc) => quote!(::std::option::Option::Some(heap_arc(&self.#member))),
+            None => quote!(::std::option::Option::None),
+        };
+        quote_spanned! {ty.span()=>
+            fields.push(Field {
+                name: #name,
+                ty: #shown,
+                offset: ::std::mem::offset_of!(Self, #member),
+                size: ::std::mem::size_of_val(&self.#member),
+                align: ::std::mem::align_of_val(&self.#member),
+                heap: #heap,
+            });
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Where this value's SPACE lives: each field's offset, size and
+            /// alignment, the padding between them, and the heap SPACE owned
+            /// through `Box`, `Vec`, `String`, `Rc` and `Arc` fields.
+            #[allow(dead_code)]
+            pub fn space_report(&self) -> ::std::string::String {
+                use ::std::string::{String, ToString};
+                use ::std::vec::Vec;
+
+                struct Heap { what: String, bytes: usize, at: *const u8, owner: String }
+                struct Field { name: &'static str, ty: &'static str, offset: usize, size: usize, align: usize, heap: ::std::option::Option<Heap> }
+
+                fn heap_box<T: ?Sized>(value: &::std::boxed::Box<T>) -> Heap {
+                    Heap {
+                        what: short(::std::any::type_name::<T>()),
+                        bytes: ::std::mem::size_of_val(&**value),
+                        at: (&**value as *const T).cast(),
+                        owner: "owner".to_string(),
+                    }
+                }
+                fn heap_vec<T>(value: &::std::vec::Vec<T>) -> Heap {
+                    Heap {
+                        what: ::std::format!("[{}] {} of {} slots used", short(::std::any::type_name::<T>()), value.len(), value.capacity()),
+                        bytes: value.capacity() * ::std::mem::size_of::<T>(),
+                        at: value.as_ptr().cast(),
+                        owner: "owner".to_string(),
+                    }
+                }
+                fn heap_string(value: &::std::string::String) -> Heap {
+                    Heap {
+                        what: ::std::format!("{:?} {} of {} bytes used", value, value.len(), value.capacity()),
+                        bytes: value.capacity(),
+                        at: value.as_ptr(),
+                        owner: "owner".to_string(),
+                    }
+                }
+                // Counted SPACE keeps its two counts in front of the value
+                fn heap_rc<T: ?Sized>(value: &::std::rc::Rc<T>) -> Heap {
+                    Heap {
+                        what: ::std::format!("strong + weak counts, {}", short(::std::any::type_name::<T>())),
+                        bytes: 2 * ::std::mem::size_of::<usize>() + ::std::mem::size_of_val(&**value),
+                        at: ::std::rc::Rc::as_ptr(value).cast(),
+                        owner: ::std::format!("co_owner, 1 of {} owner tags", ::std::rc::Rc::strong_count(value)),
+                    }
+                }
+                fn heap_arc<T: ?Sized>(value: &::std::sync::Arc<T>) -> Heap {
+                    Heap {
+                        what: ::std::format!("strong + weak counts, {}", short(::std::any::type_name::<T>())),
+                        bytes: 2 * ::std::mem::size_of::<usize>() + ::std::mem::size_of_val(&**value),
+                        at: ::std::sync::Arc::as_ptr(value).cast(),
+                        owner: ::std::format!("co_owner, 1 of {} owner tags", ::std::sync::Arc::strong_count(value)),
+                    }
+                }
+                // `alloc::string::String` → `String`, inside generics too
+                fn short(name: &str) -> String {
+                    let mut out = String::new();
+                    let mut path = String::new();
+                    for c in name.chars().chain(::std::iter::once(' ')) {
+                        if c.is_alphanumeric() || c == '_' || c == ':' {
+                            path.push(c);
+                        } else {
+                            out.push_str(path.rsplit("::").next().unwrap_or(""));
+                            path.clear();
+                            out.push(c);
+                        }
+                    }
+                    out.trim_end().to_string()
+                }
+                fn boxed(lines: &mut Vec<String>, rows: &[(String, String)]) {
+                    if rows.is_empty() {
+                        lines.push("(empty)".to_string());
+                        return;
+                    }
+                    let width = rows.iter().map(|(text, _)| text.chars().count()).max().unwrap_or(0) + 2;
+                    let rule = "─".repeat(width);
+                    lines.push(::std::format!("┌{rule}┐"));
+                    for (i, (text, notes)) in rows.iter().enumerate() {
+                        if i > 0 {
+                            lines.push(::std::format!("├{rule}┤"));
+                        }
+                        let row = ::std::format!("│ {text:<0$} │ {notes}", width - 2);
+                        lines.push(row.trim_end().to_string());
+                    }
+                    lines.push(::std::format!("└{rule}┘"));
+                }
+
+                let mut fields: Vec<Field> = Vec::new();
+                #(#fields)*
+                let declared: Vec<&str> = fields.iter().map(|field| field.name).collect();
+                fields.sort_by_key(|field| field.offset);
+
+                let size = ::std::mem::size_of::<Self>();
+                let mut lines = ::std::vec![::std::format!(
+                    "{} at {:p}: {} bytes, align {}",
+                    short(::std::any::type_name::<Self>()), self, size, ::std::mem::align_of::<Self>(),
+                )];
+                if fields.iter().map(|field| field.name).ne(declared.iter().copied()) {
+                    lines.push(::std::format!("(fields reordered by the compiler; declared as {})", declared.join(", ")));
+                }
+
+                // (range, field, size, alignment, notes)
+                let mut columns: Vec<(String, String, usize, ::std::option::Option<usize>, String)> = Vec::new();
+                let mut heaps: Vec<(&Field, &Heap)> = Vec::new();
+                let mut end = 0;
+                for field in &fields {
+                    if field.offset > end {
+                        columns.push((::std::format!("{end}..{}", field.offset), "(padding)".to_string(),
+                            field.offset - end, ::std::option::Option::None, String::new()));
+                    }
+                    let notes = match &field.heap {
+                        ::std::option::Option::Some(heap) if heap.bytes > 0 => {
+                            heaps.push((field, heap));
+                            ::std::format!("---> heap #{}", heaps.len())
+                        }
+                        ::std::option::Option::Some(_) => "(no heap SPACE allocated)".to_string(),
+                        ::std::option::Option::None => String::new(),
+                    };
+                    columns.push((
+                        ::std::format!("{}..{}", field.offset, field.offset + field.size),
+                        ::std::format!("{}: {}", field.name, field.ty),
+                        field.size,
+                        ::std::option::Option::Some(field.align),
+                        notes,
+                    ));
+                    end = end.max(field.offset + field.size);
+                }
+                if size > end {
+                    columns.push((::std::format!("{end}..{size}"), "(padding)".to_string(),
+                        size - end, ::std::option::Option::None, String::new()));
+                }
+
+                let range = columns.iter().map(|column| column.0.len()).max().unwrap_or(0);
+                let field = columns.iter().map(|column| column.1.chars().count()).max().unwrap_or(0);
+                let bytes = columns.iter().map(|column| column.2.to_string().len()).max().unwrap_or(0);
+                let rows: Vec<(String, String)> = columns
+                    .into_iter()
+                    .map(|(r, f, s, a, notes)| {
+                        let align = a.map(|a| ::std::format!("align {a}")).unwrap_or_default();
+                        (::std::format!("{r:<range$}  {f:<field$}  size {s:<bytes$}  {align}").trim_end().to_string(), notes)
+                    })
+                    .collect();
+                boxed(&mut lines, &rows);
+
+                for (i, (field, heap)) in heaps.iter().enumerate() {
+                    lines.push(::std::format!("(heap #{}) at {:p}: {} bytes", i + 1, heap.at, heap.bytes));
+                    boxed(&mut lines, &[(heap.what.clone(), ::std::format!("<--- {} ({})", field.name, heap.owner))]);
+                }
+                lines.join("\n")
+            }
+        }
+    })
+}
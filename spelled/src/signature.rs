@@ -0,0 +1,265 @@
+use std::mem;
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    FnArg, GenericParam, Ident, Lifetime, LifetimeParam, Receiver, ReturnType, Signature, Token, Type,
+};
+
+use crate::types::{angle_bracket, expand_type, notation_type, Kind};
+
+/// Rewrite `owner(T)` to `owner<T>` in the signature only.
+///
+/// The body (the last brace group) is left untouched, since `owner(x)` there
+/// is a pattern or expression.
+pub(crate) fn angle_bracket_signature(item: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    let body = match tokens.last() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => tokens.pop(),
+        _ => None,
+    };
+    let mut signature = angle_bracket(tokens.into_iter().collect());
+    signature.extend(body);
+    signature
+}
+
+/// Rewrite `owner(T)` to `owner<T>` in every signature of a run of items.
+///
+/// Function bodies are left untouched; the bodies of `impl`, `trait` and
+/// `mod` items are walked for the signatures inside them.
+pub(crate) fn angle_bracket_items(items: TokenStream) -> TokenStream {
+    let mut rewritten = TokenStream::new();
+    let mut signature = Vec::new();
+    let mut in_fn = false;
+    for token in items {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                rewritten.extend(angle_bracket(mem::take(&mut signature).into_iter().collect()));
+                if in_fn {
+                    rewritten.extend([TokenTree::Group(group)]);
+                    in_fn = false;
+                } else {
+                    let mut inner = Group::new(Delimiter::Brace, angle_bracket_items(group.stream()));
+                    inner.set_span(group.span());
+                    rewritten.extend([TokenTree::Group(inner)]);
+                }
+            }
+            token => {
+                match &token {
+                    TokenTree::Ident(ident) if ident == "fn" => in_fn = true,
+                    // a trait method without a default body
+                    TokenTree::Punct(punct) if punct.as_char() == ';' => in_fn = false,
+                    _ => {}
+                }
+                signature.push(token);
+            }
+        }
+    }
+    rewritten.extend(angle_bracket(signature.into_iter().collect()));
+    rewritten
+}
+
+/// `#[explicit_fn(from = a | b)]`: the parameters a `name` output borrows from.
+#[derive(Default)]
+pub(crate) struct Args {
+    from: Punctuated<Ident, Token![|]>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Args { from: Punctuated::new() });
+        }
+        let key: Ident = input.parse()?;
+        if key != "from" {
+            return Err(syn::Error::new(key.span(), "expected `from = <param>`"));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(Args {
+            from: Punctuated::parse_separated_nonempty_with(input, Ident::parse_any)?,
+        })
+    }
+}
+
+/// Rewrite the signature of a notation function, generating one lifetime that
+/// ties a `name` output to the parameters it borrows from.
+pub(crate) fn expand(args: &Args, sig: &mut Signature) -> syn::Result<()> {
+    let from: Vec<&Ident> = args.from.iter().collect();
+    let lifetime = (!from.is_empty()).then(|| {
+        // `'self` is reserved, so a borrowed receiver is called `'receiver`
+        let label = from
+            .iter()
+            .map(|ident| if *ident == "self" { "receiver".to_string() } else { ident.to_string() })
+            .collect::<Vec<_>>()
+            .join("_or_");
+        Lifetime::new(&format!("'{label}"), from[0].span())
+    });
+
+    // Raw coordinates carry no lifetime to tie, so they only make sense as struct fields
+    let inputs = sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(typed) => Some(&*typed.ty),
+        FnArg::Receiver(receiver) => receiver.colon_token.is_some().then_some(&*receiver.ty),
+    });
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => Some(&**ty),
+        ReturnType::Default => None,
+    };
+    for ty in inputs.chain(output) {
+        if let Some((kind, _)) = notation_type(ty).filter(|(kind, _)| kind.is_raw()) {
+            return Err(syn::Error::new(
+                ty.span(),
+                format!("`{}(..)` is only for #[explicit_struct] fields", kind.as_str()),
+            ));
+        }
+    }
+
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => notation_type(ty).map(|(kind, inner)| (kind, inner.clone(), ty.span())),
+        ReturnType::Default => None,
+    };
+    let output_kind = output.as_ref().map(|(kind, ..)| *kind);
+
+    // A name output must say where it borrows from; an owner output borrows from nothing
+    match (output_kind, from.first()) {
+        (Some(kind @ (Kind::NameShared | Kind::NameExclusive)), None) => {
+            let span = output.as_ref().map_or_else(Span::call_site, |(.., span)| *span);
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "`{}(..)` output must say where it borrows from: add `#[explicit_fn(from = <param>)]`",
+                    kind.as_str()
+                ),
+            ));
+        }
+        (Some(Kind::Owner) | None, Some(ident)) => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "only a `name_shared(..)` or `name_exclusive(..)` output borrows from a parameter",
+            ));
+        }
+        _ => {}
+    }
+
+    let mut errors: Option<syn::Error> = None;
+    let mut push = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    for ident in &from {
+        let param = sig.inputs.iter().find_map(|arg| match arg {
+            FnArg::Typed(typed) if is_param(&typed.pat, ident) => {
+                Some((notation_type(&typed.ty).map(|(kind, _)| kind), typed.ty.span()))
+            }
+            FnArg::Receiver(receiver) if *ident == "self" => Some((receiver_kind(receiver), receiver.span())),
+            _ => None,
+        });
+        let Some((param_kind, param_span)) = param else {
+            push(syn::Error::new(ident.span(), format!("no parameter named `{ident}` to borrow from")));
+            continue;
+        };
+        match (param_kind, output_kind) {
+            (Some(Kind::Owner), _) => push(syn::Error::new(
+                ident.span(),
+                format!("cannot borrow from `{ident}`: it is an owner(..) parameter, so its SPACE dies when the function returns"),
+            )),
+            (Some(Kind::NameShared), Some(Kind::NameExclusive)) => push(syn::Error::new(
+                ident.span(),
+                format!("cannot hand out exclusive coordinates from `{ident}`: it only names its SPACE shared"),
+            )),
+            (Some(_), _) => {}
+            (None, _) => push(syn::Error::new(
+                param_span,
+                format!("`{ident}` must be declared as `name_shared(..)` or `name_exclusive(..)` to be borrowed from"),
+            )),
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    for arg in &mut sig.inputs {
+        match arg {
+            FnArg::Typed(typed) => {
+                if let Some((kind, inner)) = notation_type(&typed.ty) {
+                    let tied = from.iter().any(|ident| is_param(&typed.pat, ident));
+                    *typed.ty = expand_type(kind, inner, lifetime.as_ref().filter(|_| tied));
+                }
+            }
+            FnArg::Receiver(receiver) => {
+                let tied = from.iter().any(|ident| *ident == "self");
+                expand_receiver(receiver, lifetime.as_ref().filter(|_| tied));
+            }
+        }
+    }
+    if let (ReturnType::Type(_, ty), Some((kind, inner, _))) = (&mut sig.output, &output) {
+        **ty = expand_type(*kind, inner, lifetime.as_ref());
+    }
+    if let Some(lifetime) = lifetime {
+        sig.generics
+            .params
+            .insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+    }
+    Ok(())
+}
+
+/// How a method receiver relates to SPACE. Plain `self`, `&self` and
+/// `&mut self` count as `owner`, `name_shared` and `name_exclusive`.
+fn receiver_kind(receiver: &Receiver) -> Option<Kind> {
+    if receiver.colon_token.is_some() {
+        return notation_type(&receiver.ty).map(|(kind, _)| kind);
+    }
+    match (&receiver.reference, &receiver.mutability) {
+        (None, _) => Some(Kind::Owner),
+        (Some(_), Some(_)) => Some(Kind::NameExclusive),
+        (Some(_), None) => Some(Kind::NameShared),
+    }
+}
+
+/// Rewrite `self: name_shared(Self)` to `&self` and so on, with `lifetime`
+/// on the reference if the output borrows from the receiver.
+///
+/// A receiver type other than `Self` keeps the long form: `self:
+/// owner(Box<Self>)` becomes `self: Box<Self>`.
+fn expand_receiver(receiver: &mut Receiver, lifetime: Option<&Lifetime>) {
+    let span = receiver.span();
+    let kind = if receiver.colon_token.is_some() {
+        match notation_type(&receiver.ty) {
+            Some((kind, inner)) if !is_self_type(inner) => {
+                let ty = expand_type(kind, inner, lifetime);
+                let mutability = receiver.mutability;
+                *receiver = syn::parse_quote_spanned! {span=> #mutability self: #ty };
+                return;
+            }
+            Some((kind, _)) => kind,
+            None => return,
+        }
+    } else if lifetime.is_some() {
+        match receiver_kind(receiver) {
+            Some(kind) => kind,
+            None => return,
+        }
+    } else {
+        return;
+    };
+    *receiver = match kind {
+        Kind::NameShared => syn::parse_quote_spanned! {span=> &#lifetime self },
+        Kind::NameExclusive => syn::parse_quote_spanned! {span=> &#lifetime mut self },
+        _ => {
+            let mutability = receiver.mutability;
+            syn::parse_quote_spanned! {span=> #mutability self }
+        }
+    };
+}
+
+fn is_self_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+}
+
+fn is_param(pat: &syn::Pat, ident: &Ident) -> bool {
+    matches!(pat, syn::Pat::Ident(pat) if pat.ident == *ident)
+}
+
@@ -0,0 +1,164 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::Parser,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Field, Fields, GenericParam, ItemStruct, Lifetime, LifetimeParam, Type,
+};
+
+use crate::types::{expand_type, notation_type, Kind};
+
+/// Rewrites every notation type inside one field, tying names to `lifetime`.
+struct FieldRewriter<'a> {
+    lifetime: &'a Lifetime,
+    /// Notation found in the field, with the inner types already rewritten.
+    found: Vec<(Kind, Type)>,
+}
+
+impl VisitMut for FieldRewriter<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let Some((kind, inner)) = notation_type(ty) else {
+            return visit_mut::visit_type_mut(self, ty);
+        };
+        let mut inner = inner.clone();
+        self.visit_type_mut(&mut inner);
+        *ty = expand_type(kind, &inner, Some(self.lifetime));
+        self.found.push((kind, inner));
+    }
+}
+
+/// Rewrite the fields of a notation struct.
+///
+/// Every field that names outside SPACE gets its own lifetime, named after the
+/// field. Raw coordinates carry no lifetime, so a `PhantomData` field ties them.
+/// Returns an impl with a `SPACE_SUMMARY` describing which fields tie the
+/// struct to outside SPACE; the same summary is appended to the struct's docs.
+pub(crate) fn expand(item: &mut ItemStruct) -> syn::Result<TokenStream> {
+    let mut lifetimes = Vec::new();
+    let mut phantoms = Vec::new();
+    let mut lines = Vec::new();
+    let mut tied = Vec::new();
+
+    for (index, field) in item.fields.iter_mut().enumerate() {
+        let label = field.ident.as_ref().map_or_else(|| index.to_string(), ToString::to_string);
+        let lifetime_name = field.ident.as_ref().map_or_else(|| format!("field{index}"), ToString::to_string);
+        let lifetime = Lifetime::new(&format!("'{lifetime_name}"), field.ty.span());
+
+        let written = notation_display(&field.ty);
+        let top = notation_type(&field.ty).map(|(kind, _)| kind);
+        let mut rewriter = FieldRewriter {
+            lifetime: &lifetime,
+            found: Vec::new(),
+        };
+        rewriter.visit_type_mut(&mut field.ty);
+
+        let names = rewriter.found.iter().any(|(kind, _)| *kind != Kind::Owner);
+        let meaning = match (top, names) {
+            (Some(Kind::Owner), false) => "owns its SPACE, dropped with the struct".to_string(),
+            (None, false) => "written in plain Rust".to_string(),
+            (Some(Kind::NameShared), _) => format!("names SPACE owned elsewhere, shared: the struct cannot outlive it ({lifetime})"),
+            (Some(Kind::NameExclusive), _) => format!(
+                "names SPACE owned elsewhere, exclusive: nothing else uses it while the struct lives ({lifetime})"
+            ),
+            (Some(Kind::NameSharedRaw | Kind::NameExclusiveRaw), _) => {
+                format!("raw coordinates into SPACE owned elsewhere, tied by PhantomData ({lifetime})")
+            }
+            (_, true) => format!("contains names of SPACE owned elsewhere ({lifetime})"),
+        };
+        lines.push(format!("`{label}`: {written}: {meaning}"));
+
+        if names {
+            tied.push(format!("`{label}`"));
+            lifetimes.push(lifetime.clone());
+        }
+        let raw: Vec<TokenStream> = rewriter
+            .found
+            .iter()
+            .filter_map(|(kind, inner)| match kind {
+                Kind::NameSharedRaw => Some(quote! { &#lifetime #inner }),
+                Kind::NameExclusiveRaw => Some(quote! { &#lifetime mut #inner }),
+                _ => None,
+            })
+            .collect();
+        if !raw.is_empty() {
+            let ty = match raw.as_slice() {
+                [single] => quote! { ::std::marker::PhantomData<#single> },
+                many => quote! { ::std::marker::PhantomData<(#(#many),*)> },
+            };
+            let ident = syn::Ident::new(&format!("_{lifetime_name}"), field.ty.span());
+            phantoms.push((ident, ty));
+        }
+    }
+
+    for (ident, ty) in phantoms {
+        match &mut item.fields {
+            Fields::Named(fields) => fields.named.push(Field::parse_named.parse2(quote! { #ident: #ty })?),
+            Fields::Unnamed(fields) => fields.unnamed.push(Field::parse_unnamed.parse2(ty)?),
+            Fields::Unit => {}
+        }
+    }
+    for (index, lifetime) in lifetimes.into_iter().enumerate() {
+        item.generics
+            .params
+            .insert(index, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+    }
+
+    let name = &item.ident;
+    let header = if tied.is_empty() {
+        format!("{name} owns all of its SPACE")
+    } else {
+        format!("{name} is tied to outside SPACE through {}", tied.join(", "))
+    };
+    let summary = std::iter::once(header.clone())
+        .chain(lines.iter().map(|line| format!("- {line}")))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let docs = ["".to_string(), format!(" {header}:"), "".to_string()]
+        .into_iter()
+        .chain(lines.iter().map(|line| format!(" - {line}")));
+    for doc in docs {
+        item.attrs.push(syn::parse_quote! { #[doc = #doc] });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Which fields own SPACE and which tie the struct to SPACE owned elsewhere.
+            pub const SPACE_SUMMARY: &'static str = #summary;
+        }
+    })
+}
+
+/// Turns `owner<T>` back into `owner(T)` for display.
+struct Unbracket;
+
+impl VisitMut for Unbracket {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        visit_mut::visit_type_mut(self, ty);
+        if let Some((kind, inner)) = notation_type(ty) {
+            let marker = syn::Ident::new(kind.as_str(), ty.span());
+            *ty = Type::Verbatim(quote! { #marker(#inner) });
+        }
+    }
+}
+
+/// The field type as written in notation, e.g. `name_shared(str)`.
+fn notation_display(ty: &Type) -> String {
+    let mut ty = ty.clone();
+    Unbracket.visit_type_mut(&mut ty);
+    ty.to_token_stream()
+        .to_string()
+        .replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" :: ", "::")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+        .replace(" (", "(")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+}
@@ -0,0 +1,470 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    Expr, Pat, Stmt, Token,
+};
+
+use crate::vocabulary::{self, Marker, Rewrite};
+use crate::{get_ident_name, is_path_ident};
+
+/// `explicit_trace!(sink = path; ..)`: where events go, stderr if not given.
+pub(crate) struct Input {
+    sink: Option<Expr>,
+    pub(crate) stmts: Vec<Stmt>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let sink = if input.peek(syn::Ident) && input.peek2(Token![=]) && input.fork().parse::<Ident>()? == "sink" {
+            input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            let sink = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Some(sink)
+        } else {
+            None
+        };
+        Ok(Input {
+            sink,
+            stmts: syn::Block::parse_within(input)?,
+        })
+    }
+}
+
+impl Input {
+    /// Statements that set up the sink and the drop trackers for the traced block.
+    pub(crate) fn prelude(&self) -> TokenStream {
+        let span = Span::mixed_site();
+        let sink = match &self.sink {
+            Some(sink) => quote! { #sink },
+            None => quote_spanned! {span=> {
+                fn stderr(event: &str) { ::std::eprintln!("[space] {}", event) }
+                stderr
+            }},
+        };
+        quote_spanned! {span=>
+            let __spelled_sink: fn(&str) = #sink;
+            // Declared right after its owner, a tracker is dropped right before it
+            let __spelled_track = {
+                struct Tracker {
+                    sink: fn(&str),
+                    moved: ::std::rc::Rc<::std::cell::Cell<bool>>,
+                    dropped: &'static str,
+                    taken: &'static str,
+                }
+                impl ::std::ops::Drop for Tracker {
+                    fn drop(&mut self) {
+                        (self.sink)(if self.moved.get() { self.taken } else { self.dropped })
+                    }
+                }
+                fn track(
+                    sink: fn(&str),
+                    dropped: &'static str,
+                    taken: &'static str,
+                ) -> (::std::rc::Rc<::std::cell::Cell<bool>>, ::std::boxed::Box<dyn ::std::any::Any>) {
+                    let moved = ::std::rc::Rc::new(::std::cell::Cell::new(false));
+                    (moved.clone(), ::std::boxed::Box::new(Tracker { sink, moved, dropped, taken }))
+                }
+                track
+            };
+        }
+    }
+}
+
+/// Instruments notation statements with events, before they are rewritten.
+///
+/// Each statement gets its events as sibling statements, so bindings stay in
+/// scope. Owners get a tracker declared right after them; it reports the drop
+/// the compiler inserts at scope end, or that nothing was left to drop once the
+/// owner's SPACE was taken. An owner of a `Copy` value has nothing dropped
+/// for it, which its tracker reports instead.
+#[derive(Default)]
+pub(crate) struct Tracer {
+    /// Bindings in scope, innermost last.
+    scopes: Vec<Vec<(String, Tracked)>>,
+}
+
+/// What the trace keeps about a binding.
+#[derive(Clone, Copy, PartialEq)]
+enum Tracked {
+    /// A name, or a binding inside a destructuring pattern.
+    No,
+    /// An owner with a tracker.
+    Owner,
+    /// An owner with a tracker, and whether its value is `Copy` in scope.
+    Probed,
+}
+
+impl VisitMut for Tracer {
+    fn visit_block_mut(&mut self, block: &mut syn::Block) {
+        self.instrument(&mut block.stmts);
+    }
+}
+
+impl Tracer {
+    pub(crate) fn instrument(&mut self, stmts: &mut Vec<Stmt>) {
+        self.scopes.push(Vec::new());
+        for mut stmt in std::mem::take(stmts) {
+            visit_mut::visit_stmt_mut(self, &mut stmt);
+
+            let mut scan = MoveScan::default();
+            match &stmt {
+                Stmt::Local(local) => {
+                    if let Some(init) = &local.init {
+                        // `let _ = x;` binds nothing, so x keeps its SPACE
+                        if !is_wild(&local.pat) {
+                            scan.moved_by_value(&init.expr);
+                        }
+                        scan.visit_expr(&init.expr);
+                    }
+                }
+                Stmt::Expr(expr, _) => scan.visit_expr(expr),
+                Stmt::Item(_) | Stmt::Macro(_) => {}
+            }
+
+            let span = Span::mixed_site();
+            for (ident, certain) in &scan.moves {
+                let copied = copied_ident(ident);
+                if !certain {
+                    let probe = copy_probe(ident);
+                    stmts.push(syn::parse_quote_spanned! {span=> let #copied = #probe; });
+                }
+                if self.is_tracked(ident) {
+                    let trace = trace_ident(ident);
+                    stmts.push(if *certain {
+                        syn::parse_quote_spanned! {span=> #trace.0.set(true); }
+                    } else {
+                        syn::parse_quote_spanned! {span=> if !#copied { #trace.0.set(true); } }
+                    });
+                }
+            }
+            for ident in &scan.dropped {
+                if self.is_tracked(ident) {
+                    stmts.push(emit(format!("{ident} is dropped explicitly: its SPACE is freed")));
+                }
+            }
+
+            let mut after = Vec::new();
+            match &mut stmt {
+                Stmt::Local(local) => {
+                    let init = local.init.as_ref().map(|init| &*init.expr);
+                    match binding(&local.pat) {
+                        Some((marker, ident)) => {
+                            if let Some(event) = binding_event(marker, &ident, init, &scan) {
+                                after.push(event);
+                            }
+                            let tracked = match (marker, marker.scope_end()) {
+                                (_, None) => Tracked::No,
+                                // Only an initialized owner can be probed
+                                (Marker::Owner, _) if init.is_some() => Tracked::Probed,
+                                _ => Tracked::Owner,
+                            };
+                            if let Some(scope_end) = marker.scope_end() {
+                                let trace = trace_ident(&ident);
+                                let dropped = format!("{ident} goes out of scope: {scope_end}");
+                                let taken = format!("{ident} goes out of scope: nothing left to drop");
+                                let dropped = if tracked == Tracked::Probed {
+                                    let is_copy = is_copy_ident(&ident);
+                                    let probe = copy_probe(&ident);
+                                    let copied = format!("{ident} goes out of scope: it is Copy, nothing to drop");
+                                    after.push(syn::parse_quote_spanned! {span=> let #is_copy = #probe; });
+                                    quote_spanned! {span=> if #is_copy { #copied } else { #dropped } }
+                                } else {
+                                    quote! { #dropped }
+                                };
+                                after.push(syn::parse_quote_spanned! {span=>
+                                    let #trace = __spelled_track(__spelled_sink, #dropped, #taken);
+                                });
+                            }
+                            self.declare(ident.to_string(), tracked);
+                        }
+                        None => {
+                            let mut bound = Bindings::default();
+                            bound.visit_pat(&local.pat);
+                            for ident in bound.0 {
+                                self.declare(ident, Tracked::No);
+                            }
+                        }
+                    }
+                }
+                // x = value → the old SPACE is dropped, unless it was taken
+                Stmt::Expr(Expr::Assign(assign), semi) => {
+                    if let Some(ident) = bare_ident(&assign.left).filter(|ident| self.is_tracked(ident)) {
+                        let trace = trace_ident(&ident);
+                        let dropped = format!("{ident} rebinds: its old SPACE is dropped");
+                        let taken = format!("{ident} rebinds: its old SPACE was already taken");
+                        stmts.push(if self.tracked(&ident) == Tracked::Probed {
+                            let is_copy = is_copy_ident(&ident);
+                            let copied = format!("{ident} rebinds: it is Copy, nothing to drop");
+                            syn::parse_quote_spanned! {span=>
+                                __spelled_sink(if #is_copy { #copied } else if #trace.0.get() { #taken } else { #dropped });
+                            }
+                        } else {
+                            syn::parse_quote_spanned! {span=>
+                                __spelled_sink(if #trace.0.get() { #taken } else { #dropped });
+                            }
+                        });
+                        after.push(syn::parse_quote_spanned! {span=> #trace.0.set(false); });
+                        semi.get_or_insert_with(Default::default);
+                    }
+                }
+                _ => {}
+            }
+
+            stmts.push(stmt);
+            stmts.extend(after);
+        }
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: String, tracked: Tracked) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((name, tracked));
+        }
+    }
+
+    fn tracked(&self, ident: &Ident) -> Tracked {
+        let name = ident.to_string();
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|(bound, _)| *bound == name)
+            .map_or(Tracked::No, |(_, tracked)| *tracked)
+    }
+
+    fn is_tracked(&self, ident: &Ident) -> bool {
+        self.tracked(ident) != Tracked::No
+    }
+}
+
+/// What one statement does to the bindings it mentions.
+///
+/// Only the statement itself is scanned: nested blocks, closures and match
+/// arms may not run, and blocks are instrumented on their own.
+#[derive(Default)]
+struct MoveScan {
+    /// Bare identifiers moved out of; `true` when the notation says so, `false`
+    /// when it depends on whether the type is `Copy`.
+    moves: Vec<(Ident, bool)>,
+    dropped: Vec<Ident>,
+}
+
+impl MoveScan {
+    fn moved(&mut self, ident: Ident, certain: bool) {
+        if !self.moves.iter().any(|(moved, _)| *moved == ident) {
+            self.moves.push((ident, certain));
+        }
+    }
+
+    fn moved_by_value(&mut self, expr: &Expr) {
+        if let Some(ident) = bare_ident(expr) {
+            self.moved(ident, false);
+        }
+    }
+
+    fn copied(&self, ident: &Ident) -> bool {
+        self.moves.iter().any(|(moved, certain)| moved == ident && !certain)
+    }
+}
+
+impl<'ast> Visit<'ast> for MoveScan {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        match get_ident_name(&call.func).as_deref() {
+            Some("take") => {
+                if let Some(ident) = call.args.first().and_then(bare_ident) {
+                    self.moved(ident, true);
+                }
+            }
+            Some("drop") => {
+                if let Some(ident) = call.args.first().and_then(bare_ident) {
+                    self.dropped.push(ident.clone());
+                    self.moved(ident, true);
+                }
+            }
+            // Notation that only reads or names its argument
+            Some(op) if vocabulary::lookup(op).is_some_and(|op| op.borrows) => {}
+            _ => call.args.iter().for_each(|arg| self.moved_by_value(arg)),
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        call.args.iter().for_each(|arg| self.moved_by_value(arg));
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_tuple(&mut self, tuple: &'ast syn::ExprTuple) {
+        tuple.elems.iter().for_each(|elem| self.moved_by_value(elem));
+        visit::visit_expr_tuple(self, tuple);
+    }
+
+    fn visit_expr_array(&mut self, array: &'ast syn::ExprArray) {
+        array.elems.iter().for_each(|elem| self.moved_by_value(elem));
+        visit::visit_expr_array(self, array);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        expr.fields.iter().for_each(|field| self.moved_by_value(&field.expr));
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_expr_assign(&mut self, assign: &'ast syn::ExprAssign) {
+        self.moved_by_value(&assign.right);
+        visit::visit_expr_assign(self, assign);
+    }
+
+    fn visit_block(&mut self, _: &'ast syn::Block) {}
+
+    fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+
+    fn visit_arm(&mut self, _: &'ast syn::Arm) {}
+}
+
+/// Every identifier a pattern binds.
+#[derive(Default)]
+struct Bindings(Vec<String>);
+
+impl<'ast> Visit<'ast> for Bindings {
+    fn visit_pat_ident(&mut self, ident: &'ast syn::PatIdent) {
+        self.0.push(ident.ident.to_string());
+        visit::visit_pat_ident(self, ident);
+    }
+}
+
+/// `_` or `_: T`, which binds nothing.
+fn is_wild(pat: &Pat) -> bool {
+    match pat {
+        Pat::Type(typed) => is_wild(&typed.pat),
+        pat => matches!(pat, Pat::Wild(_)),
+    }
+}
+
+/// `owner(x)`, `name(rebindable(x))`, `guard(g): T`, ...: the marker and the binding.
+fn binding(pat: &Pat) -> Option<(Marker, Ident)> {
+    let pat = match pat {
+        Pat::Type(typed) => &*typed.pat,
+        pat => pat,
+    };
+    let Pat::TupleStruct(ts) = pat else {
+        return None;
+    };
+    let marker = crate::marker_of(ts)?;
+    let inner = match ts.elems.first()? {
+        Pat::TupleStruct(inner) if is_path_ident(&inner.path, "rebindable") => inner.elems.first()?,
+        inner => inner,
+    };
+    match inner {
+        Pat::Ident(ident) if ident.subpat.is_none() => Some((marker, ident.ident.clone())),
+        _ => None,
+    }
+}
+
+/// The event a `let` narrates, in the model's terms.
+fn binding_event(marker: Marker, ident: &Ident, init: Option<&Expr>, scan: &MoveScan) -> Option<Stmt> {
+    let Some(init) = init else {
+        return Some(emit(format!("{ident} is declared; it has no SPACE yet")));
+    };
+    let (op, arg) = match init {
+        Expr::Call(call) => (get_ident_name(&call.func), call.args.first()),
+        _ => (None, None),
+    };
+    let source = arg.map_or_else(|| "a value".to_string(), describe);
+    let (x, y) = (ident.to_string(), source.as_str());
+    let event = match op.as_deref().and_then(vocabulary::lookup) {
+        // The argument itself: whether it was taken or copied is only known
+        // at runtime, so narrate whichever happened
+        Some(op) if matches!(op.rewrite, Rewrite::Same) => {
+            match arg.and_then(bare_ident).filter(|arg| scan.copied(arg)) {
+                Some(arg) => {
+                    let copied = copied_ident(&arg);
+                    let event = |name| vocabulary::lookup(name).expect("registered").event(&x, y);
+                    let (took, copy) = (event("take"), event("mem_copy"));
+                    let span = Span::mixed_site();
+                    return Some(syn::parse_quote_spanned! {span=>
+                        __spelled_sink(if #copied { #copy } else { #took });
+                    });
+                }
+                None => op.event(&x, y),
+            }
+        }
+        Some(op) => op.event(&x, y),
+        None if marker == Marker::Name => format!("{ident} names SPACE owned elsewhere"),
+        None => match bare_ident(init) {
+            Some(source) => format!("{ident} takes or copies {source}"),
+            None => format!("{ident} owns new SPACE"),
+        },
+    };
+    Some(emit(event))
+}
+
+fn emit(event: String) -> Stmt {
+    let span = Span::mixed_site();
+    syn::parse_quote_spanned! {span=> __spelled_sink(#event); }
+}
+
+/// `{ ..; (&probe(&x)).is_copy() }`: whether `x` is `Copy`, decided by autoref specialization.
+fn copy_probe(ident: &Ident) -> TokenStream {
+    let span = Span::mixed_site();
+    quote_spanned! {span=> {
+        struct CopyProbe<T>(::std::marker::PhantomData<T>);
+        #[allow(dead_code)]
+        trait IsCopy { fn is_copy(&self) -> bool { true } }
+        impl<T: ::std::marker::Copy> IsCopy for CopyProbe<T> {}
+        #[allow(dead_code)]
+        trait IsMove { fn is_copy(&self) -> bool { false } }
+        impl<T> IsMove for &CopyProbe<T> {}
+        fn probe<T>(_: &T) -> CopyProbe<T> { CopyProbe(::std::marker::PhantomData) }
+        (&probe(&#ident)).is_copy()
+    }}
+}
+
+fn trace_ident(ident: &Ident) -> Ident {
+    Ident::new(&format!("__{ident}_trace"), Span::mixed_site())
+}
+
+fn is_copy_ident(ident: &Ident) -> Ident {
+    Ident::new(&format!("__{ident}_is_copy"), Span::mixed_site())
+}
+
+fn copied_ident(ident: &Ident) -> Ident {
+    Ident::new(&format!("__{ident}_copied"), Span::mixed_site())
+}
+
+fn bare_ident(expr: &Expr) -> Option<Ident> {
+    match expr {
+        Expr::Path(path) if path.qself.is_none() => path.path.get_ident().cloned(),
+        Expr::Paren(paren) => bare_ident(&paren.expr),
+        _ => None,
+    }
+}
+
+/// `x`, `p.field`, `v[0]` as written, for event text.
+fn describe(expr: &Expr) -> String {
+    match expr {
+        Expr::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        Expr::Field(field) => match &field.member {
+            syn::Member::Named(name) => format!("{}.{name}", describe(&field.base)),
+            syn::Member::Unnamed(index) => format!("{}.{}", describe(&field.base), index.index),
+        },
+        Expr::Index(index) => match &*index.index {
+            Expr::Lit(lit) => format!("{}[{}]", describe(&index.expr), quote!(#lit)),
+            _ => format!("{}[..]", describe(&index.expr)),
+        },
+        Expr::Paren(paren) => describe(&paren.expr),
+        Expr::Call(call) => match get_ident_name(&call.func) {
+            Some(op) => format!("{op}({})", call.args.first().map_or_else(String::new, describe)),
+            None => "a value".to_string(),
+        },
+        _ => "a value".to_string(),
+    }
+}
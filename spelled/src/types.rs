@@ -0,0 +1,105 @@
+use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
+use syn::{spanned::Spanned, GenericArgument, Lifetime, PathArguments, Type};
+
+/// How a parameter, output or field relates to SPACE.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Owner,
+    NameShared,
+    NameExclusive,
+    /// Raw coordinates that still name outside SPACE for a lifetime.
+    NameSharedRaw,
+    NameExclusiveRaw,
+}
+
+impl Kind {
+    const ALL: [Kind; 5] = [
+        Kind::Owner,
+        Kind::NameShared,
+        Kind::NameExclusive,
+        Kind::NameSharedRaw,
+        Kind::NameExclusiveRaw,
+    ];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Kind::Owner => "owner",
+            Kind::NameShared => "name_shared",
+            Kind::NameExclusive => "name_exclusive",
+            Kind::NameSharedRaw => "name_shared_raw",
+            Kind::NameExclusiveRaw => "name_exclusive_raw",
+        }
+    }
+
+    pub(crate) fn is_raw(self) -> bool {
+        matches!(self, Kind::NameSharedRaw | Kind::NameExclusiveRaw)
+    }
+}
+
+/// Rewrite `owner(T)` to `owner<T>` so syn can parse it as a type.
+///
+/// rustc accepts parenthesized arguments on any type path, but syn only does
+/// for trait bounds.
+pub(crate) fn angle_bracket(tokens: TokenStream) -> TokenStream {
+    let mut rewritten = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if Kind::ALL.iter().any(|kind| ident == kind.as_str()) => {
+                let args = tokens.next_if(|next| {
+                    matches!(next, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)
+                });
+                rewritten.push(TokenTree::Ident(ident));
+                if let Some(TokenTree::Group(group)) = args {
+                    let mut open = Punct::new('<', Spacing::Alone);
+                    open.set_span(group.span_open());
+                    let mut close = Punct::new('>', Spacing::Alone);
+                    close.set_span(group.span_close());
+                    rewritten.push(TokenTree::Punct(open));
+                    rewritten.extend(angle_bracket(group.stream()));
+                    rewritten.push(TokenTree::Punct(close));
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut inner = Group::new(group.delimiter(), angle_bracket(group.stream()));
+                inner.set_span(group.span());
+                rewritten.push(TokenTree::Group(inner));
+            }
+            other => rewritten.push(other),
+        }
+    }
+    rewritten.into_iter().collect()
+}
+
+/// `owner<T>`, `name_shared<T>`, ... as rewritten from `owner(T)` by [`angle_bracket`].
+pub(crate) fn notation_type(ty: &Type) -> Option<(Kind, &Type)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() || path.path.segments.len() != 1 {
+        return None;
+    }
+    let segment = &path.path.segments[0];
+    let kind = *Kind::ALL.iter().find(|kind| segment.ident == kind.as_str())?;
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some((kind, inner)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Rewrite a notation type into Rust, with `lifetime` on references if given.
+///
+/// Raw coordinates carry no lifetime; whoever holds them ties it separately.
+pub(crate) fn expand_type(kind: Kind, inner: &Type, lifetime: Option<&Lifetime>) -> Type {
+    let span = inner.span();
+    match kind {
+        Kind::Owner => inner.clone(),
+        Kind::NameShared => syn::parse_quote_spanned! {span=> &#lifetime #inner },
+        Kind::NameExclusive => syn::parse_quote_spanned! {span=> &#lifetime mut #inner },
+        Kind::NameSharedRaw => syn::parse_quote_spanned! {span=> *const #inner },
+        Kind::NameExclusiveRaw => syn::parse_quote_spanned! {span=> *mut #inner },
+    }
+}
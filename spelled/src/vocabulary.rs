@@ -0,0 +1,486 @@
+//! The notation's operations, in one place.
+//!
+//! `transform_expr` rewrites every call it finds here; the combination check,
+//! capture lists and traces read what each one binds and whether it leaves its
+//! argument valid. The `vocabulary` tool renders the same entries as the tables
+//! in `src/vocabulary.md` and `src/combinations.md` (part of the `explicit!`
+//! docs) and in the course chapters. Add an operation here and regenerate with
+//! `cargo run -p vocabulary`.
+//!
+//! This file has no dependencies so the tool can include it by path. Each
+//! side reads only part of it: the macro the rewrites, the tool the text.
+#![allow(dead_code)]
+
+/// What an operation does to SPACE, which also groups the tables.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Category {
+    Transfer,
+    Coordinates,
+    Counted,
+    Runtime,
+    Raw,
+}
+
+impl Category {
+    pub(crate) const ALL: [Category; 5] = [
+        Category::Transfer,
+        Category::Coordinates,
+        Category::Counted,
+        Category::Runtime,
+        Category::Raw,
+    ];
+
+    /// The key used to select the category in a course marker.
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Category::Transfer => "transfer",
+            Category::Coordinates => "coordinates",
+            Category::Counted => "counted",
+            Category::Runtime => "runtime",
+            Category::Raw => "raw",
+        }
+    }
+
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            Category::Transfer => "Transferring and copying SPACE",
+            Category::Coordinates => "Coordinates",
+            Category::Counted => "Counted SPACE",
+            Category::Runtime => "Coordinates checked at runtime",
+            Category::Raw => "Raw coordinates",
+        }
+    }
+}
+
+/// What a binding is declared as, `owner(x)`: what it holds of its SPACE.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Marker {
+    Owner,
+    CoOwner,
+    Name,
+    Guard,
+}
+
+impl Marker {
+    pub(crate) const ALL: [Marker; 4] = [Marker::Owner, Marker::CoOwner, Marker::Name, Marker::Guard];
+
+    pub(crate) fn of(name: &str) -> Option<Marker> {
+        Marker::ALL.into_iter().find(|marker| marker.name() == name)
+    }
+
+    /// As it is written, `co_owner`.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Marker::Owner => "owner",
+            Marker::CoOwner => "co_owner",
+            Marker::Name => "name",
+            Marker::Guard => "guard",
+        }
+    }
+
+    /// What the end of the binding's scope does to SPACE; `None` for a name,
+    /// which owns nothing.
+    pub(crate) fn scope_end(self) -> Option<&'static str> {
+        match self {
+            Marker::Owner => Some("its SPACE is dropped"),
+            Marker::CoOwner => Some("one owner tag is dropped"),
+            Marker::Name => None,
+            Marker::Guard => Some("the borrow ends"),
+        }
+    }
+}
+
+/// The marker of a binding that receives an operation's result.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Binds {
+    Owner,
+    CoOwner,
+    Name,
+    Guard,
+    /// The value itself, so whatever it was bound with: `owner(..)` for
+    /// plain SPACE, but a guard or an owner tag can be taken too.
+    Moved,
+    /// Nothing: the call is a statement.
+    Nothing,
+}
+
+impl Binds {
+    /// The marker a capture list binds the capture with.
+    pub(crate) fn marker(self) -> Marker {
+        match self {
+            Binds::Owner | Binds::Moved | Binds::Nothing => Marker::Owner,
+            Binds::CoOwner => Marker::CoOwner,
+            Binds::Name => Marker::Name,
+            Binds::Guard => Marker::Guard,
+        }
+    }
+}
+
+/// How the macro rewrites a call, given its (already rewritten) arguments.
+#[derive(Clone, Copy)]
+pub(crate) enum Rewrite {
+    /// Tokens before the argument: `&`, `&mut`, `*`.
+    Prefix(&'static str),
+    /// A function around the argument: `Box::new`.
+    Call(&'static str),
+    /// Tokens after the argument: `.borrow()`.
+    Suffix(&'static str),
+    /// A method on the first argument taking the second: `cell.set(v)`.
+    Method(&'static str),
+    /// The argument itself.
+    Same,
+    /// An expansion the macro builds itself, to check the value's type or
+    /// dispatch on it.
+    Generated(Generated),
+}
+
+impl Rewrite {
+    pub(crate) fn arity(self) -> usize {
+        match self {
+            Rewrite::Method(_) => 2,
+            _ => 1,
+        }
+    }
+
+    /// For a rewrite to checked coordinates, `&y` or `&mut y`: whether they
+    /// are exclusive.
+    pub(crate) fn coordinates(self) -> Option<bool> {
+        match self {
+            Rewrite::Prefix("&mut") => Some(true),
+            Rewrite::Prefix(prefix) if prefix.starts_with('&') && !prefix.starts_with("&raw") => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Generated {
+    MemCopy,
+    Take,
+    CloneCopy,
+    ShareOwner,
+    CoordWeak,
+    TryAt,
+}
+
+pub(crate) struct Op {
+    pub(crate) name: &'static str,
+    pub(crate) category: Category,
+    /// The call as it is written, `coord_shared(y)`.
+    pub(crate) notation: &'static str,
+    /// The Rust it stands for, `&y`.
+    pub(crate) rust: &'static str,
+    pub(crate) meaning: &'static str,
+    pub(crate) rewrite: Rewrite,
+    pub(crate) binds: Binds,
+    /// Whether the argument stays valid: the operation only reads or names it.
+    pub(crate) borrows: bool,
+    /// Whether a closure's capture list accepts it.
+    pub(crate) captures: bool,
+    /// Its row of the "Valid combinations" table: the binding receiving the
+    /// result as it is written, `owner(x)`, and what the pairing means.
+    pub(crate) combination: Option<(&'static str, &'static str)>,
+    /// What `explicit_trace!` reports for `let x = op(y)`, with `{x}` and
+    /// `{y}` standing for the binding and the argument.
+    pub(crate) event: &'static str,
+}
+
+impl Op {
+    /// The trace event for binding `x` to this operation on `y`.
+    pub(crate) fn event(&self, x: &str, y: &str) -> String {
+        self.event.replace("{x}", x).replace("{y}", y)
+    }
+}
+
+pub(crate) const OPS: &[Op] = &[
+    Op {
+        name: "take",
+        category: Category::Transfer,
+        notation: "take(y)",
+        rust: "y",
+        meaning: "transfers ownership; y becomes invalid (non-Copy types)",
+        rewrite: Rewrite::Generated(Generated::Take),
+        binds: Binds::Moved,
+        borrows: false,
+        captures: true,
+        combination: Some(("owner(x)", "the owner tag transfers from y to x")),
+        event: "{x} takes SPACE from {y} ({y} invalid)",
+    },
+    Op {
+        name: "mem_copy",
+        category: Category::Transfer,
+        notation: "mem_copy(y)",
+        rust: "y",
+        meaning: "duplicates the bytes; y stays valid (Copy types)",
+        rewrite: Rewrite::Generated(Generated::MemCopy),
+        binds: Binds::Owner,
+        borrows: true,
+        captures: true,
+        combination: Some(("owner(x)", "new SPACE, x owns it, y unchanged")),
+        event: "{x} gets a copy of {y}'s bytes ({y} still valid)",
+    },
+    Op {
+        name: "take_or_mem_copy",
+        category: Category::Transfer,
+        notation: "take_or_mem_copy(y)",
+        rust: "y",
+        meaning: "the compiler decides: Copy types are copied, everything else is taken",
+        rewrite: Rewrite::Same,
+        binds: Binds::Moved,
+        borrows: false,
+        captures: true,
+        combination: Some(("owner(x)", "x owns (new or transferred)")),
+        event: "{x} takes or copies {y}",
+    },
+    Op {
+        name: "clone_copy",
+        category: Category::Transfer,
+        notation: "clone_copy(y)",
+        rust: "y.clone()",
+        meaning: "new SPACE from `.clone()`; y stays valid (Clone types)",
+        rewrite: Rewrite::Generated(Generated::CloneCopy),
+        binds: Binds::Owner,
+        borrows: true,
+        captures: true,
+        combination: Some(("owner(x)", "new SPACE from .clone(), x owns it")),
+        event: "{x} owns a clone of {y}'s SPACE",
+    },
+    Op {
+        name: "coord_shared",
+        category: Category::Coordinates,
+        notation: "coord_shared(y)",
+        rust: "&y",
+        meaning: "shared coordinates; many allowed at the same time",
+        rewrite: Rewrite::Prefix("&"),
+        binds: Binds::Name,
+        borrows: true,
+        captures: true,
+        combination: Some(("name(r)", "r borrows y's SPACE (shared)")),
+        event: "{x} names {y}'s SPACE (shared)",
+    },
+    Op {
+        name: "coord_exclusive",
+        category: Category::Coordinates,
+        notation: "coord_exclusive(y)",
+        rust: "&mut y",
+        meaning: "exclusive coordinates; only one allowed",
+        rewrite: Rewrite::Prefix("&mut"),
+        binds: Binds::Name,
+        borrows: true,
+        captures: true,
+        combination: Some(("name(r)", "r borrows y's SPACE (exclusive)")),
+        event: "{x} names {y}'s SPACE (exclusive)",
+    },
+    Op {
+        name: "at",
+        category: Category::Coordinates,
+        notation: "at(r)",
+        rust: "*r",
+        meaning: "follows coordinates to the value",
+        rewrite: Rewrite::Prefix("*"),
+        binds: Binds::Owner,
+        borrows: true,
+        captures: false,
+        combination: None,
+        event: "{x} gets the value at {y}",
+    },
+    Op {
+        name: "coord_heap",
+        category: Category::Coordinates,
+        notation: "coord_heap(y)",
+        rust: "Box::new(y)",
+        meaning: "new SPACE on the heap, owned through coordinates",
+        rewrite: Rewrite::Call("Box::new"),
+        binds: Binds::Owner,
+        borrows: false,
+        captures: false,
+        combination: Some(("owner(b)", "new heap SPACE, b owns it through coordinates")),
+        event: "{x} owns new heap SPACE",
+    },
+    Op {
+        name: "coord_counted",
+        category: Category::Counted,
+        notation: "coord_counted(y)",
+        rust: "Rc::new(y)",
+        meaning: "counted SPACE with several owner tags, one thread",
+        rewrite: Rewrite::Call("::std::rc::Rc::new"),
+        binds: Binds::CoOwner,
+        borrows: false,
+        captures: false,
+        combination: Some(("co_owner(a)", "new counted SPACE, a holds the first owner tag")),
+        event: "{x} holds the first owner tag of new counted SPACE",
+    },
+    Op {
+        name: "coord_counted_atomic",
+        category: Category::Counted,
+        notation: "coord_counted_atomic(y)",
+        rust: "Arc::new(y)",
+        meaning: "counted SPACE with several owner tags, across threads",
+        rewrite: Rewrite::Call("::std::sync::Arc::new"),
+        binds: Binds::CoOwner,
+        borrows: false,
+        captures: false,
+        combination: Some(("co_owner(a)", "new counted SPACE shared across threads, a holds the first owner tag")),
+        event: "{x} holds the first owner tag of new atomically counted SPACE",
+    },
+    Op {
+        name: "share_owner",
+        category: Category::Counted,
+        notation: "share_owner(rc)",
+        rust: "Rc::clone(&rc) / Arc::clone(&rc)",
+        meaning: "one more owner tag of counted SPACE",
+        rewrite: Rewrite::Generated(Generated::ShareOwner),
+        binds: Binds::CoOwner,
+        borrows: true,
+        captures: true,
+        combination: Some(("co_owner(b)", "b holds another owner tag of rc's SPACE")),
+        event: "{x} holds another owner tag of {y}'s SPACE",
+    },
+    Op {
+        name: "coord_weak",
+        category: Category::Counted,
+        notation: "coord_weak(rc)",
+        rust: "Rc::downgrade(&rc) / Arc::downgrade(&rc)",
+        meaning: "coordinates to counted SPACE without an owner tag",
+        rewrite: Rewrite::Generated(Generated::CoordWeak),
+        binds: Binds::Name,
+        borrows: true,
+        captures: true,
+        combination: Some(("name(w)", "w names rc's SPACE without keeping it alive")),
+        event: "{x} names {y}'s SPACE without keeping it alive",
+    },
+    Op {
+        name: "try_at",
+        category: Category::Counted,
+        notation: "try_at(w)",
+        rust: "w.upgrade()",
+        meaning: "an owner tag, if the SPACE is still alive",
+        rewrite: Rewrite::Generated(Generated::TryAt),
+        binds: Binds::CoOwner,
+        borrows: true,
+        captures: false,
+        combination: Some(("Some(co_owner(s))", "s holds another owner tag, if the SPACE is still alive")),
+        event: "{x} tries to get an owner tag back from {y}",
+    },
+    Op {
+        name: "coord_shared_runtime",
+        category: Category::Runtime,
+        notation: "coord_shared_runtime(c)",
+        rust: "c.borrow()",
+        meaning: "RefCell: shared, many allowed, checked at runtime",
+        rewrite: Rewrite::Suffix(".borrow()"),
+        binds: Binds::Guard,
+        borrows: true,
+        captures: false,
+        combination: Some(("guard(g)", "g is a shared borrow of c until it is dropped")),
+        event: "{x} borrows {y} at runtime (shared) until it is dropped",
+    },
+    Op {
+        name: "coord_exclusive_runtime",
+        category: Category::Runtime,
+        notation: "coord_exclusive_runtime(c)",
+        rust: "c.borrow_mut()",
+        meaning: "RefCell: exclusive, one allowed, checked at runtime",
+        rewrite: Rewrite::Suffix(".borrow_mut()"),
+        binds: Binds::Guard,
+        borrows: true,
+        captures: false,
+        combination: Some(("guard(rebindable(g))", "g is the exclusive borrow of c until it is dropped")),
+        event: "{x} borrows {y} at runtime (exclusive) until it is dropped",
+    },
+    Op {
+        name: "coord_shared_locked",
+        category: Category::Runtime,
+        notation: "coord_shared_locked(rw)",
+        rust: "rw.read().unwrap()",
+        meaning: "RwLock: shared, many allowed, waits",
+        rewrite: Rewrite::Suffix(".read().unwrap()"),
+        binds: Binds::Guard,
+        borrows: true,
+        captures: false,
+        combination: Some(("guard(g)", "g holds a read lock on rw until it is dropped")),
+        event: "{x} holds a read lock on {y} until it is dropped",
+    },
+    Op {
+        name: "coord_exclusive_locked",
+        category: Category::Runtime,
+        notation: "coord_exclusive_locked(m)",
+        rust: "m.lock().unwrap()",
+        meaning: "Mutex: exclusive, one allowed, waits",
+        rewrite: Rewrite::Suffix(".lock().unwrap()"),
+        binds: Binds::Guard,
+        borrows: true,
+        captures: false,
+        combination: Some(("guard(rebindable(g))", "g holds m's lock until it is dropped")),
+        event: "{x} holds {y}'s lock until it is dropped",
+    },
+    Op {
+        name: "swap_in",
+        category: Category::Runtime,
+        notation: "swap_in(cell, v)",
+        rust: "cell.set(v)",
+        meaning: "Cell: replaces the value, no coordinates handed out",
+        rewrite: Rewrite::Method("set"),
+        binds: Binds::Nothing,
+        borrows: true,
+        captures: false,
+        combination: None,
+        event: "{x} holds nothing: {y}'s value is replaced in place",
+    },
+    Op {
+        name: "coord_raw",
+        category: Category::Raw,
+        notation: "coord_raw(x)",
+        rust: "&raw const x",
+        meaning: "raw coordinates, shared",
+        rewrite: Rewrite::Prefix("&raw const"),
+        binds: Binds::Name,
+        borrows: true,
+        captures: false,
+        combination: None,
+        event: "{x} holds raw coordinates of {y}'s SPACE (shared)",
+    },
+    Op {
+        name: "coord_raw_exclusive",
+        category: Category::Raw,
+        notation: "coord_raw_exclusive(x)",
+        rust: "&raw mut x",
+        meaning: "raw coordinates, exclusive",
+        rewrite: Rewrite::Prefix("&raw mut"),
+        binds: Binds::Name,
+        borrows: true,
+        captures: false,
+        combination: None,
+        event: "{x} holds raw coordinates of {y}'s SPACE (exclusive)",
+    },
+    Op {
+        name: "at_unchecked",
+        category: Category::Raw,
+        notation: "at_unchecked(p)",
+        rust: "*p",
+        meaning: "value at raw coordinates, unchecked",
+        rewrite: Rewrite::Prefix("*"),
+        binds: Binds::Owner,
+        borrows: true,
+        captures: false,
+        combination: None,
+        event: "{x} gets the value at {y}, unchecked",
+    },
+    Op {
+        name: "coord_from_raw",
+        category: Category::Raw,
+        notation: "coord_from_raw(p)",
+        rust: "&*p",
+        meaning: "checked coordinates from raw ones, unchecked",
+        rewrite: Rewrite::Prefix("&*"),
+        binds: Binds::Name,
+        borrows: true,
+        captures: false,
+        combination: None,
+        event: "{x} names the SPACE at {y}, unchecked",
+    },
+];
+
+pub(crate) fn lookup(name: &str) -> Option<&'static Op> {
+    OPS.iter().find(|op| op.name == name)
+}
@@ -0,0 +1,21 @@
+use spelled::explicit;
+
+fn square(n: i32) -> i32 {
+    n * n
+}
+
+static OFFSET: i32 = 1;
+
+#[test]
+fn functions_and_statics_are_not_captures() {
+    explicit! {
+        let owner(nums) = vec![1, 2, 3];
+        let owner(sum_squares) = capture(take(nums)) || nums.into_iter().map(square).sum::<i32>() + offset(OFFSET);
+        let owner(total) = sum_squares();
+    }
+    assert_eq!(total, 15);
+}
+
+fn offset(n: i32) -> i32 {
+    n
+}
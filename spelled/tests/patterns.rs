@@ -0,0 +1,29 @@
+use spelled::explicit;
+
+const LIMIT: u32 = 10;
+
+#[test]
+fn unit_variants_stay_patterns_under_coordinates() {
+    explicit! {
+        let owner(o) = Some(None::<String>);
+        let owner(state) = match coord_shared(o) {
+            Some(None) => "empty",
+            Some(Some(name(_s))) => "full",
+            None => "nothing",
+        };
+    }
+    assert_eq!(state, "empty");
+}
+
+#[test]
+fn constants_stay_patterns_under_coordinates() {
+    explicit! {
+        let owner(rebindable(o)) = Some(10u32);
+        let owner(at_limit) = match coord_exclusive(o) {
+            Some(LIMIT) => true,
+            Some(name(n)) => { *n += 1; false }
+            None => false,
+        };
+    }
+    assert!(at_limit);
+}
@@ -0,0 +1,6 @@
+// The notation's compile errors; each case pins the message a learner sees.
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
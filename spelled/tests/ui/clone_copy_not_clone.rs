@@ -0,0 +1,10 @@
+use spelled::explicit;
+
+struct Token;
+
+fn main() {
+    explicit! {
+        let owner(t) = Token;
+        let owner(u) = clone_copy(t);
+    }
+}
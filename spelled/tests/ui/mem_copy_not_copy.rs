@@ -0,0 +1,8 @@
+use spelled::explicit;
+
+fn main() {
+    explicit! {
+        let owner(s) = String::from("hello");
+        let owner(t) = mem_copy(s);
+    }
+}
@@ -0,0 +1,9 @@
+use spelled::explicit;
+
+fn main() {
+    explicit! {
+        let owner(pair) = (String::from("label"), 3);
+        let (name_of(label), name(count)) = coord_shared(pair);
+        let (name_of(rebindable(first)), owner(second)) = pair;
+    }
+}
@@ -0,0 +1,8 @@
+use spelled::explicit;
+
+fn main() {
+    explicit! {
+        let owner(x) = String::from("hello");
+        let name(r) = take(x);
+    }
+}
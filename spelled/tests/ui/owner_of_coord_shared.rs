@@ -0,0 +1,8 @@
+use spelled::explicit;
+
+fn main() {
+    explicit! {
+        let owner(x) = String::from("hello");
+        let owner(y) = coord_shared(x);
+    }
+}
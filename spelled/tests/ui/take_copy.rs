@@ -0,0 +1,8 @@
+use spelled::explicit;
+
+fn main() {
+    explicit! {
+        let owner(x) = 5;
+        let owner(y) = take(x);
+    }
+}
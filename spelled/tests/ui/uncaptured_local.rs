@@ -0,0 +1,13 @@
+use spelled::explicit;
+
+fn square(n: i32) -> i32 {
+    n * n
+}
+
+fn main() {
+    explicit! {
+        let owner(nums) = vec![1, 2, 3];
+        let owner(factor) = 2;
+        let owner(scaled) = capture(take(nums)) || nums.iter().map(|n| square(*n) * factor).sum::<i32>();
+    }
+}
@@ -0,0 +1,176 @@
+//! Render the operation tables from `spelled/src/vocabulary.rs`.
+//!
+//! ```text
+//! vocabulary [--check]
+//! ```
+//!
+//! Writes `spelled/src/vocabulary.md` and `spelled/src/combinations.md`, which
+//! the `explicit!` docs include, and regenerates every region of the course
+//! chapters between
+//!
+//! ```text
+//! <!-- vocabulary: take coord_shared runtime -->
+//! <!-- /vocabulary -->
+//! ```
+//!
+//! where the marker lists operation names, category keys or `all`.
+//!
+//! With `--check`, nothing is written. The tool exits with an error listing
+//! each file that is out of date, so CI fails when the course disagrees with
+//! the macro.
+
+#[path = "../../src/vocabulary.rs"]
+mod vocabulary;
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use vocabulary::{Category, Op, OPS};
+
+const OPEN: &str = "<!-- vocabulary:";
+const CLOSE: &str = "<!-- /vocabulary -->";
+
+fn main() {
+    let mut check = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            _ => {
+                eprintln!("usage: vocabulary [--check]");
+                process::exit(1);
+            }
+        }
+    }
+
+    let spelled = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let course = spelled.parent().unwrap().join("course");
+    let mut outputs = vec![
+        (spelled.join("src/vocabulary.md"), reference()),
+        (spelled.join("src/combinations.md"), combinations()),
+    ];
+    for path in chapters(&course) {
+        let source = read(&path);
+        let updated = regions(&source).unwrap_or_else(|e| {
+            eprintln!("vocabulary: {}: {e}", path.display());
+            process::exit(1);
+        });
+        outputs.push((path, updated));
+    }
+
+    let mut stale = Vec::new();
+    for (path, text) in outputs {
+        if fs::read_to_string(&path).ok().as_deref() == Some(text.as_str()) {
+            continue;
+        }
+        if !check {
+            if let Err(e) = fs::write(&path, text) {
+                eprintln!("vocabulary: cannot write {}: {e}", path.display());
+                process::exit(1);
+            }
+            println!("vocabulary: {} regenerated", path.display());
+        }
+        stale.push(path);
+    }
+    if check && !stale.is_empty() {
+        for path in &stale {
+            eprintln!("vocabulary: {} is out of date", path.display());
+        }
+        eprintln!("run `cargo run -p vocabulary` to regenerate");
+        process::exit(1);
+    }
+}
+
+/// The full reference included in the `explicit!` docs: one table per category.
+fn reference() -> String {
+    let mut text = String::from("<!-- generated by `cargo run -p vocabulary`; edit src/vocabulary.rs -->\n");
+    for category in Category::ALL {
+        let ops: Vec<&Op> = OPS.iter().filter(|op| op.category == category).collect();
+        text.push_str(&format!("\n### {}\n\n", category.title()));
+        text.push_str(&table(&ops));
+    }
+    text
+}
+
+/// The "Valid combinations" table: each `let` a receiving binding makes with an operation.
+fn combinations() -> String {
+    let mut text = String::from("<!-- generated by `cargo run -p vocabulary`; edit src/vocabulary.rs -->\n\n");
+    text.push_str("| Left | Right | Meaning |\n|------|-------|---------|\n");
+    for op in OPS {
+        if let Some((left, meaning)) = op.combination {
+            text.push_str(&format!("| `{left}` | `{}` | {meaning} |\n", op.notation));
+        }
+    }
+    text
+}
+
+fn table(ops: &[&Op]) -> String {
+    let mut text = String::from("| Notation | Rust | Meaning |\n|----------|------|---------|\n");
+    for op in ops {
+        text.push_str(&format!("| `{}` | `{}` | {} |\n", op.notation, op.rust, op.meaning));
+    }
+    text
+}
+
+/// The operations a marker selects, in registry order.
+fn select(keys: &str) -> Result<Vec<&'static Op>, String> {
+    let keys: Vec<&str> = keys.split_whitespace().collect();
+    for key in &keys {
+        let known = *key == "all"
+            || OPS.iter().any(|op| op.name == *key)
+            || Category::ALL.iter().any(|category| category.key() == *key);
+        if !known {
+            return Err(format!("unknown operation or category `{key}`"));
+        }
+    }
+    Ok(OPS
+        .iter()
+        .filter(|op| {
+            keys.iter().any(|key| *key == "all" || *key == op.name || *key == op.category.key())
+        })
+        .collect())
+}
+
+/// Regenerate the table inside every marked region of a chapter.
+fn regions(source: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = source;
+    while let Some(start) = rest.find(OPEN) {
+        let line_end = rest[start..].find('\n').map_or(rest.len(), |i| start + i + 1);
+        let marker = rest[start..line_end].trim_end();
+        let keys = marker
+            .strip_prefix(OPEN)
+            .and_then(|keys| keys.strip_suffix("-->"))
+            .ok_or_else(|| format!("malformed marker `{marker}`"))?;
+        let end = rest[line_end..]
+            .find(CLOSE)
+            .map(|i| line_end + i)
+            .ok_or_else(|| format!("`{marker}` has no closing `{CLOSE}`"))?;
+        out.push_str(&rest[..line_end]);
+        out.push('\n');
+        out.push_str(&table(&select(keys)?));
+        out.push('\n');
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn chapters(dir: &Path) -> Vec<PathBuf> {
+    let entries = fs::read_dir(dir).unwrap_or_else(|e| {
+        eprintln!("vocabulary: cannot read {}: {e}", dir.display());
+        process::exit(1);
+    });
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("vocabulary: cannot read {}: {e}", path.display());
+        process::exit(1);
+    })
+}
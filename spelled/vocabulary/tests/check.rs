@@ -0,0 +1,13 @@
+// The reference tables and the course's generated regions are written from
+// src/vocabulary.rs; an operation added there without regenerating fails here.
+
+use std::process::Command;
+
+#[test]
+fn generated_files_are_current() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vocabulary"))
+        .arg("--check")
+        .output()
+        .expect("vocabulary runs");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}